@@ -1,18 +1,85 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::{parse::Parser, punctuated::Punctuated, Token};
 
 fn token_stream_with_error(mut tokens: TokenStream, error: syn::Error) -> TokenStream {
     tokens.extend(TokenStream::from(error.into_compile_error()));
     tokens
 }
 
+fn lit_str(expr: &syn::Expr) -> syn::Result<syn::LitStr> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        }) => Ok(value.clone()),
+        expr => Err(syn::Error::new_spanned(expr, "expected a string literal")),
+    }
+}
+
+/// Options passed to `#[task(...)]`.
+#[derive(Default)]
+struct TaskAttrs {
+    name: Option<syn::LitStr>,
+    about: Option<syn::LitStr>,
+    depends_on: Option<Vec<syn::LitStr>>,
+}
+
+impl TaskAttrs {
+    fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        let parser = Punctuated::<syn::Expr, Token![,]>::parse_terminated;
+        for option in parser.parse(attr)? {
+            let syn::Expr::Assign(option) = option else {
+                return Err(syn::Error::new_spanned(option, "expected `option = value`"));
+            };
+            let key = match option.left.as_ref() {
+                syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                    path.path.get_ident().cloned().unwrap()
+                }
+                key => return Err(syn::Error::new_spanned(key, "expected an option name")),
+            };
+            if key == "name" {
+                attrs.name = Some(lit_str(&option.right)?);
+            } else if key == "about" {
+                attrs.about = Some(lit_str(&option.right)?);
+            } else if key == "depends_on" {
+                let syn::Expr::Array(deps) = option.right.as_ref() else {
+                    return Err(syn::Error::new_spanned(
+                        option.right,
+                        "expected an array of task names",
+                    ));
+                };
+                attrs.depends_on =
+                    Some(deps.elems.iter().map(lit_str).collect::<syn::Result<_>>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "unknown task option, expected `name`, `about` or `depends_on`",
+                ));
+            }
+        }
+        Ok(attrs)
+    }
+
+    /// Tasks with options are registered in the task inventory instead of generating `main`.
+    fn is_registered(&self) -> bool {
+        self.name.is_some() || self.about.is_some() || self.depends_on.is_some()
+    }
+}
+
 #[proc_macro_attribute]
-pub fn task(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn task(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input: syn::ItemFn = match syn::parse(item.clone()) {
         Ok(input) => input,
         Err(error) => return token_stream_with_error(item, error),
     };
 
+    let attrs = match TaskAttrs::parse(attr) {
+        Ok(attrs) => attrs,
+        Err(error) => return token_stream_with_error(item, error),
+    };
+
     if input.sig.asyncness.is_none() {
         return token_stream_with_error(
             item,
@@ -23,28 +90,65 @@ pub fn task(_attr: TokenStream, item: TokenStream) -> TokenStream {
         );
     }
 
-    if input.sig.inputs.len() != 1 {
+    if input.sig.inputs.is_empty() || input.sig.inputs.len() > 2 {
         return token_stream_with_error(
             item,
             syn::Error::new_spanned(
                 input.sig,
-                "the #[task] attribute can only be applied to a function with a single argument",
+                "the #[task] attribute can only be applied to a function taking `&mut Env` and optionally a `clap::Args` struct",
             ),
         );
     }
 
+    let args_ty = match input.sig.inputs.iter().nth(1) {
+        Some(syn::FnArg::Typed(arg)) => Some(arg.ty.clone()),
+        Some(arg) => {
+            return token_stream_with_error(
+                item,
+                syn::Error::new_spanned(arg, "the task arguments must be a typed argument"),
+            )
+        }
+        None => None,
+    };
+
+    let output = if attrs.is_registered() {
+        registered_task(&input, &attrs, args_ty)
+    } else {
+        task_main(&input, args_ty)
+    };
+    output.into()
+}
+
+/// Generate a `main` function running a single task.
+fn task_main(input: &syn::ItemFn, args_ty: Option<Box<syn::Type>>) -> proc_macro2::TokenStream {
     let input_fn = input.sig.ident.clone();
 
-    let output = quote! {
+    let (parse_args, call) = match args_ty {
+        Some(args_ty) => (
+            quote! {
+                let (task_args, args) = ::aquarium::internal::parse_task_args::<#args_ty>();
+            },
+            quote! { #input_fn(&mut env, args).await },
+        ),
+        None => (
+            quote! {
+                let task_args = <::aquarium::internal::task::TaskArgs as ::aquarium::internal::clap::Parser>::parse();
+            },
+            quote! { #input_fn(&mut env).await },
+        ),
+    };
+
+    quote! {
         #input
-        
+
         fn main () -> ::aquarium::internal::AnyhowResult<()> {
             let body = async {
+                #parse_args
                 let proj = ::aquarium::internal::Project::load()?;
-                let mut env = proj.env()?;
-                #input_fn(&mut env).await;
-                env.save_refs()?;
-                Ok(())
+                let mut env = ::aquarium::internal::EnvGuard::new(proj.env_from_args(&task_args)?);
+                let result = ::aquarium::internal::TaskOutput::into_result(#call);
+                env.finish()?;
+                result
             };
             #[allow(clippy::expect_used, clippy::diverging_sub_expression)]
             {
@@ -55,6 +159,257 @@ pub fn task(_attr: TokenStream, item: TokenStream) -> TokenStream {
                     .block_on(body);
             }
         }
+    }
+}
+
+/// Register the task in the inventory read by `aquarium::run_tasks`.
+fn registered_task(
+    input: &syn::ItemFn,
+    attrs: &TaskAttrs,
+    args_ty: Option<Box<syn::Type>>,
+) -> proc_macro2::TokenStream {
+    let input_fn = input.sig.ident.clone();
+    let run_fn = format_ident!("__aquarium_run_{}", input_fn);
+    let name = attrs
+        .name
+        .clone()
+        .unwrap_or_else(|| syn::LitStr::new(&input_fn.to_string(), input_fn.span()));
+    let about = attrs
+        .about
+        .clone()
+        .unwrap_or_else(|| syn::LitStr::new("", input_fn.span()));
+    let depends_on = attrs.depends_on.clone().unwrap_or_default();
+
+    let (augment_args, call) = match args_ty {
+        Some(args_ty) => (
+            quote! { <#args_ty as ::aquarium::internal::clap::Args>::augment_args },
+            quote! {
+                let args = <#args_ty as ::aquarium::internal::clap::FromArgMatches>::from_arg_matches(matches)?;
+                #input_fn(env, args).await
+            },
+        ),
+        None => (
+            quote! { ::std::convert::identity },
+            quote! {
+                let _ = matches;
+                #input_fn(env).await
+            },
+        ),
     };
-    output.into()
+
+    quote! {
+        #input
+
+        #[doc(hidden)]
+        fn #run_fn<'a>(
+            env: &'a mut ::aquarium::Env,
+            matches: &'a ::aquarium::internal::clap::ArgMatches,
+        ) -> ::aquarium::internal::TaskFuture<'a> {
+            ::std::boxed::Box::pin(async move {
+                let output = { #call };
+                ::aquarium::internal::TaskOutput::into_result(output)
+            })
+        }
+
+        ::aquarium::internal::inventory::submit! {
+            ::aquarium::internal::TaskDefinition {
+                name: #name,
+                about: #about,
+                depends_on: &[#(#depends_on),*],
+                args: #augment_args,
+                run: #run_fn,
+            }
+        }
+    }
+}
+
+/// `UpdateConfig` -> `update_config`, matching `#[serde(rename_all = "snake_case")]`.
+fn snake_case(ident: &syn::Ident) -> syn::Ident {
+    let name = ident.to_string();
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    // Keywords such as `move` aren't valid identifiers on their own.
+    match syn::parse_str::<syn::Ident>(&out) {
+        Ok(_) => format_ident!("{}", out, span = ident.span()),
+        Err(_) => format_ident!("r#{}", out, span = ident.span()),
+    }
+}
+
+/// The name of the generated trait, from `#[contract_client(name = "...")]` if given.
+fn client_trait_name(input: &syn::DeriveInput) -> syn::Result<syn::Ident> {
+    let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("contract_client"))
+    else {
+        return Ok(format_ident!("{}Client", input.ident));
+    };
+    let option = attr.parse_args::<syn::Expr>()?;
+    match &option {
+        syn::Expr::Assign(assign) if matches!(assign.left.as_ref(), syn::Expr::Path(p) if p.path.is_ident("name")) => {
+            lit_str(&assign.right)?.parse()
+        }
+        _ => Err(syn::Error::new_spanned(
+            option,
+            "unknown option, expected `name = \"...\"`",
+        )),
+    }
+}
+
+/// Generates a trait with one async method per variant of a contract's `ExecuteMsg` or
+/// `QueryMsg`, implemented for `aquarium::ContractHandle`.
+///
+/// Enums whose variants are annotated with `#[returns(...)]`, as for `cosmwasm-schema`'s
+/// `QueryResponses`, produce query methods returning that type. Other enums produce execute
+/// methods that take the funds to send.
+#[proc_macro_derive(ContractClient, attributes(returns, contract_client))]
+pub fn contract_client(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    match derive_contract_client(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn derive_contract_client(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "ContractClient can only be derived for message enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "ContractClient does not support generic messages",
+        ));
+    }
+
+    let returns = data
+        .variants
+        .iter()
+        .map(|variant| {
+            variant
+                .attrs
+                .iter()
+                .find(|attr| attr.path.is_ident("returns"))
+                .map(|attr| attr.parse_args::<syn::Type>())
+                .transpose()
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let is_query = returns.iter().any(Option::is_some);
+
+    let msg = &input.ident;
+    let vis = &input.vis;
+    let trait_name = client_trait_name(input)?;
+    let mut signatures = vec![];
+    let mut bodies = vec![];
+    for (variant, returns) in data.variants.iter().zip(returns) {
+        let variant_ident = &variant.ident;
+        let method = snake_case(variant_ident);
+        let docs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .collect::<Vec<_>>();
+        let funds = match &variant.fields {
+            syn::Fields::Named(fields)
+                if fields
+                    .named
+                    .iter()
+                    .any(|f| f.ident.as_ref().unwrap() == "funds") =>
+            {
+                format_ident!("attached_funds")
+            }
+            _ => format_ident!("funds"),
+        };
+        let (params, value) = match &variant.fields {
+            syn::Fields::Unit => (vec![], quote! { #msg::#variant_ident }),
+            syn::Fields::Named(fields) => {
+                let names = fields.named.iter().map(|f| f.ident.clone().unwrap());
+                let params = fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        let (name, ty) = (&f.ident, &f.ty);
+                        quote! { #name: #ty }
+                    })
+                    .collect();
+                (params, quote! { #msg::#variant_ident { #(#names),* } })
+            }
+            syn::Fields::Unnamed(fields) => {
+                let names = (0..fields.unnamed.len())
+                    .map(|i| format_ident!("arg{}", i))
+                    .collect::<Vec<_>>();
+                let params = fields
+                    .unnamed
+                    .iter()
+                    .zip(&names)
+                    .map(|(f, name)| {
+                        let ty = &f.ty;
+                        quote! { #name: #ty }
+                    })
+                    .collect();
+                (params, quote! { #msg::#variant_ident(#(#names),*) })
+            }
+        };
+
+        if is_query {
+            let Some(returns) = returns else {
+                return Err(syn::Error::new_spanned(
+                    variant_ident,
+                    "missing #[returns(...)], every query variant needs a response type",
+                ));
+            };
+            signatures.push(quote! {
+                #(#docs)*
+                async fn #method<Q: ::aquarium::Querier + Sync>(
+                    &self,
+                    __querier: &Q,
+                    #(#params),*
+                ) -> ::aquarium::internal::AnyhowResult<#returns>
+            });
+            bodies.push(quote! {
+                __querier.query(self.address.clone(), &#value).await
+            });
+        } else {
+            signatures.push(quote! {
+                #(#docs)*
+                async fn #method<E: ::aquarium::Executor + Sync>(
+                    &self,
+                    __executor: &E,
+                    #(#params,)*
+                    #funds: Vec<::aquarium::internal::cosmwasm_std::Coin>
+                ) -> ::aquarium::internal::AnyhowResult<String>
+            });
+            bodies.push(quote! {
+                __executor
+                    .execute_smart(self.address.clone(), &#value, #funds, None)
+                    .await
+            });
+        }
+    }
+
+    let doc = format!("Methods for each variant of [`{msg}`], on an `aquarium::ContractHandle`.");
+    Ok(quote! {
+        #[doc = #doc]
+        #[::aquarium::internal::async_trait::async_trait]
+        #vis trait #trait_name {
+            #(#signatures;)*
+        }
+
+        #[::aquarium::internal::async_trait::async_trait]
+        impl #trait_name for ::aquarium::ContractHandle {
+            #(#signatures { #bodies })*
+        }
+    })
 }
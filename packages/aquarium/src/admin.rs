@@ -0,0 +1,89 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::{
+    deploy::{find_instance, LABEL_ATTR},
+    Env, Querier,
+};
+
+/// The address of a contract given as `name` for its latest instance in `contracts.json`,
+/// `name:label` for its latest instance with that label, or an address.
+pub fn resolve_contract(env: &Env, reference: &str) -> Result<String> {
+    if let Some((name, label)) = reference.split_once(':') {
+        return find_instance(env, name, label)
+            .map(|instance| instance.address.clone())
+            .ok_or(anyhow::anyhow!(
+                "No instance of {name} labelled \"{label}\" in contracts.json"
+            ));
+    }
+    match env.refs.contracts.get(reference) {
+        Some(contract) => contract
+            .instances
+            .last()
+            .map(|instance| instance.address.clone())
+            .ok_or(anyhow::anyhow!(
+                "{reference} has no instances in contracts.json"
+            )),
+        None => Ok(reference.to_string()),
+    }
+}
+
+/// The on-chain state of a contract instance in `contracts.json`, as checked by [`audit`].
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub contract: String,
+    pub label: Option<String>,
+    pub address: String,
+    pub admin: Option<String>,
+    /// What differs from the expected admin and `contracts.json`, empty if nothing does.
+    pub issues: Vec<String>,
+}
+
+/// Check every instance in `contracts.json` against its on-chain contract info, flagging
+/// admins other than `expected_admin` and code IDs that differ from the refs.
+pub async fn audit<Q: Querier + Sync, E>(
+    env: &Env<Q, E>,
+    expected_admin: &str,
+) -> Result<Vec<AuditEntry>> {
+    let mut contracts = env.refs.contracts.iter().collect::<Vec<_>>();
+    contracts.sort_by_key(|(name, _)| *name);
+
+    let mut entries = vec![];
+    for (name, contract) in contracts {
+        for instance in &contract.instances {
+            let mut entry = AuditEntry {
+                contract: name.clone(),
+                label: instance
+                    .attrs
+                    .get(LABEL_ATTR)
+                    .and_then(Value::as_str)
+                    .map(|label| label.to_string()),
+                address: instance.address.clone(),
+                admin: None,
+                issues: vec![],
+            };
+            match env.querier.contract_info(instance.address.clone()).await {
+                Ok(info) => {
+                    if info.admin.as_deref() != Some(expected_admin) {
+                        entry.issues.push(format!(
+                            "admin is {}, expected {expected_admin}",
+                            info.admin.as_deref().unwrap_or("(none)")
+                        ));
+                    }
+                    if info.code_id != instance.code_id {
+                        entry.issues.push(format!(
+                            "runs code {}, contracts.json has code {}",
+                            info.code_id, instance.code_id
+                        ));
+                    }
+                    entry.admin = info.admin;
+                }
+                Err(e) => entry
+                    .issues
+                    .push(format!("could not query contract info: {e}")),
+            }
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
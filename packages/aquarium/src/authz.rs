@@ -0,0 +1,60 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::Network;
+
+/// A permission granted through authz, as reported by `cosmos/authz/v1beta1/grants`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrantInfo {
+    pub granter: String,
+    pub grantee: String,
+    /// The message type URL of a generic authorization, or else the type of authorization.
+    pub authorization: String,
+    pub expiration: Option<String>,
+}
+
+fn parse_grants(res: &Value) -> Result<Vec<GrantInfo>> {
+    let grants = res["grants"]
+        .as_array()
+        .ok_or(anyhow::anyhow!("Error parsing grants. Response: {res:#}"))?;
+    grants
+        .iter()
+        .map(|grant| {
+            let field = |field: &str| {
+                grant[field]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or(anyhow::anyhow!(
+                        "Error parsing grant {field}. Response: {res:#}"
+                    ))
+            };
+            let authorization = &grant["authorization"];
+            Ok(GrantInfo {
+                granter: field("granter")?,
+                grantee: field("grantee")?,
+                authorization: authorization["msg"]
+                    .as_str()
+                    .or(authorization["@type"].as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                expiration: grant["expiration"].as_str().map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// The grants `address` has made to other accounts.
+pub async fn grants_by(network: &Network, address: &str) -> Result<Vec<GrantInfo>> {
+    let res = network
+        .get(format!("cosmos/authz/v1beta1/grants/granter/{address}"))
+        .await?;
+    parse_grants(&res)
+}
+
+/// The grants other accounts have made to `address`.
+pub async fn grants_to(network: &Network, address: &str) -> Result<Vec<GrantInfo>> {
+    let res = network
+        .get(format!("cosmos/authz/v1beta1/grants/grantee/{address}"))
+        .await?;
+    parse_grants(&res)
+}
@@ -1,5 +1,11 @@
+use std::path::PathBuf;
+
 use clap::{Args, Parser, Subcommand};
 
+use crate::client::offline::SignMode;
+
+use super::task::TaskArgs;
+
 #[derive(Parser, Debug, Clone)]
 #[clap(author, about, version)]
 pub struct Cli {
@@ -20,6 +26,280 @@ pub enum Commands {
     Task(RunTaskArgs),
     #[clap(name = "contract", about = "Manage contracts")]
     Contract(ContractArgs),
+    #[clap(name = "deploy", about = "Apply a deployment manifest")]
+    Deploy(DeployArgs),
+    #[clap(name = "codegen", about = "Generate clients from contract schemas")]
+    Codegen(CodegenArgs),
+    #[clap(name = "export", about = "Export contract refs for other tools")]
+    Export(ExportArgs),
+    #[clap(name = "devnet", about = "Run a local chain for the project")]
+    Devnet(DevnetArgs),
+    #[clap(name = "account", about = "Manage accounts")]
+    Account(AccountArgs),
+    #[clap(
+        name = "authz",
+        about = "Grant other accounts permission to act for an account"
+    )]
+    Authz(AuthzArgs),
+    #[clap(
+        name = "tx",
+        about = "Build, sign and broadcast transactions offline, such as for multisigs"
+    )]
+    Tx(TxArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TxArgs {
+    #[clap(subcommand)]
+    pub command: TxCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum TxCommands {
+    #[clap(name = "execute", about = "Execute a contract")]
+    Execute {
+        /// A contract in contracts.json, as name or name:label, or an address
+        contract: String,
+        /// The JSON message
+        msg: String,
+        /// Coins to send along, such as 1000000ukuji
+        #[clap(long, default_value = "")]
+        funds: String,
+        #[clap(flatten)]
+        tx: TxMsgArgs,
+    },
+    #[clap(name = "migrate", about = "Migrate a contract to another code ID")]
+    Migrate {
+        /// A contract in contracts.json, as name or name:label, or an address
+        contract: String,
+        code_id: u64,
+        /// The JSON message
+        msg: String,
+        #[clap(flatten)]
+        tx: TxMsgArgs,
+    },
+    #[clap(
+        name = "update-admin",
+        about = "Make another account or address the admin of a contract"
+    )]
+    UpdateAdmin {
+        /// A contract in contracts.json, as name or name:label, or an address
+        contract: String,
+        /// An account in Aquarium.toml, or an address
+        admin: String,
+        #[clap(flatten)]
+        tx: TxMsgArgs,
+    },
+    #[clap(name = "clear-admin", about = "Remove the admin of a contract")]
+    ClearAdmin {
+        /// A contract in contracts.json, as name or name:label, or an address
+        contract: String,
+        #[clap(flatten)]
+        tx: TxMsgArgs,
+    },
+    #[clap(
+        name = "sign",
+        about = "Add the account's signature to a generated transaction"
+    )]
+    Sign {
+        /// A transaction written by --generate-only
+        file: PathBuf,
+        /// The account to sign with, defaults to the first account
+        #[clap(long, short)]
+        account: Option<String>,
+        /// Where to write the signed transaction, defaults to replacing the file
+        #[clap(long, short)]
+        out: Option<PathBuf>,
+    },
+    #[clap(
+        name = "multisign",
+        about = "Combine the signatures of several copies of a transaction"
+    )]
+    Multisign {
+        /// Copies of the same transaction, each signed by some of the signers
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+        /// Where to write the combined transaction
+        #[clap(long, short)]
+        out: PathBuf,
+    },
+    #[clap(
+        name = "broadcast",
+        about = "Broadcast a transaction once every signer has signed"
+    )]
+    Broadcast {
+        /// A signed transaction
+        file: PathBuf,
+        /// The network to use, defaults to the default network
+        #[clap(long, short)]
+        network: Option<String>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TxMsgArgs {
+    /// The account or multisig sending the transaction, defaults to --account
+    #[clap(long)]
+    pub from: Option<String>,
+    /// Write the unsigned transaction to a file instead of signing and sending it
+    #[clap(long)]
+    pub generate_only: bool,
+    /// The multisig members that will sign, as accounts or base64 public keys
+    #[clap(long, value_delimiter = ',')]
+    pub signers: Vec<String>,
+    /// The gas limit of a generated transaction
+    #[clap(long, default_value_t = 500_000)]
+    pub gas: u64,
+    /// Where to write a generated transaction, defaults to stdout
+    #[clap(long, short)]
+    pub out: Option<PathBuf>,
+    #[clap(long)]
+    pub memo: Option<String>,
+    /// How to sign, direct or amino_json, defaults to the sending account's sign mode
+    #[clap(long)]
+    pub sign_mode: Option<SignMode>,
+    #[clap(flatten)]
+    pub task: TaskArgs,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct AuthzArgs {
+    #[clap(subcommand)]
+    pub command: AuthzCommands,
+    #[clap(flatten)]
+    pub task: TaskArgs,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AuthzCommands {
+    #[clap(
+        name = "grant",
+        about = "Let another account send messages for the account"
+    )]
+    Grant {
+        /// An account in Aquarium.toml, or an address
+        grantee: String,
+        /// The type URL of the messages it may send
+        #[clap(long, default_value = "/cosmwasm.wasm.v1.MsgExecuteContract")]
+        msg: String,
+        /// How many days the grant lasts, forever if unset
+        #[clap(long)]
+        days: Option<u64>,
+    },
+    #[clap(name = "revoke", about = "Withdraw a grant made by the account")]
+    Revoke {
+        /// An account in Aquarium.toml, or an address
+        grantee: String,
+        /// The type URL of the granted messages
+        #[clap(long, default_value = "/cosmwasm.wasm.v1.MsgExecuteContract")]
+        msg: String,
+    },
+    #[clap(name = "list", about = "List the grants made by and to the account")]
+    List {},
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct AccountArgs {
+    #[clap(subcommand)]
+    pub command: AccountCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AccountCommands {
+    #[clap(
+        name = "fund",
+        about = "Send coins to an account from a faucet account"
+    )]
+    Fund {
+        /// An account in Aquarium.toml, or an address
+        name: String,
+        /// The coins to send, such as 1000000ukuji
+        #[clap(long)]
+        amount: String,
+        /// The account to send from, defaults to the network's faucet
+        #[clap(long)]
+        from: Option<String>,
+        /// The network to use, defaults to the default network
+        #[clap(long, short)]
+        network: Option<String>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DevnetArgs {
+    #[clap(subcommand)]
+    pub command: DevnetCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DevnetCommands {
+    #[clap(
+        name = "up",
+        about = "Start the devnet, creating a genesis that funds the project's accounts"
+    )]
+    Up {},
+    #[clap(name = "down", about = "Stop the devnet, keeping its state")]
+    Down {},
+    #[clap(
+        name = "reset",
+        about = "Stop the devnet, delete its state and its refs in contracts.json"
+    )]
+    Reset {},
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    #[clap(subcommand)]
+    pub command: ExportCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ExportCommands {
+    #[clap(
+        name = "ts",
+        about = "Export addresses and code IDs as TypeScript, with message types from schemas"
+    )]
+    Ts {
+        /// Where to write the files, defaults to ts in the project root
+        #[clap(long, short)]
+        out: Option<PathBuf>,
+        /// Networks to export, defaults to every network in contracts.json
+        #[clap(long, short)]
+        network: Vec<String>,
+        /// Directories holding the JSON schemas written by cosmwasm-schema
+        #[clap(long = "schema")]
+        schema_dirs: Vec<PathBuf>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CodegenArgs {
+    #[clap(subcommand)]
+    pub command: CodegenCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CodegenCommands {
+    #[clap(name = "rust", about = "Generate typed Rust clients")]
+    Rust {
+        /// Directories holding the JSON schemas written by cosmwasm-schema
+        #[clap(required = true)]
+        schema_dirs: Vec<PathBuf>,
+        /// Where to write the modules, defaults to src/contracts in the scripts crate
+        #[clap(long, short)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DeployArgs {
+    /// The manifest to apply, a .toml, .yaml or .json file
+    pub manifest: PathBuf,
+    /// Show the changes that would be made, without sending any transaction
+    #[clap(long)]
+    pub plan: bool,
+    #[clap(flatten)]
+    pub task: TaskArgs,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -36,8 +316,51 @@ pub struct ContractArgs {
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum ContractCommands {
-    #[clap(name = "new", about = "Create a new contract")]
-    List {},
+    #[clap(
+        name = "list",
+        about = "List the contracts and instances in contracts.json"
+    )]
+    List {
+        /// Only list contracts deployed to this network
+        #[clap(short, long)]
+        network: Option<String>,
+    },
+    #[clap(name = "admin", about = "Show or change the admin of a contract")]
+    Admin {
+        /// A contract in contracts.json, as name or name:label, or an address
+        contract: String,
+        #[clap(subcommand)]
+        command: AdminCommands,
+        #[clap(flatten)]
+        task: TaskArgs,
+    },
+    #[clap(
+        name = "audit",
+        about = "Check the admin and code of every contract in contracts.json on chain"
+    )]
+    Audit {
+        /// The expected admin, an account or an address, defaults to the network's admin
+        #[clap(long)]
+        admin: Option<String>,
+        #[clap(flatten)]
+        task: TaskArgs,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AdminCommands {
+    #[clap(name = "show", about = "Show the admin")]
+    Show {},
+    #[clap(name = "set", about = "Make another account or address the admin")]
+    Set {
+        /// An account in Aquarium.toml, or an address
+        admin: String,
+    },
+    #[clap(
+        name = "clear",
+        about = "Remove the admin, so the contract can't be migrated again"
+    )]
+    Clear {},
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -46,4 +369,9 @@ pub enum TaskCommands {
     List {},
     #[clap(name = "run", about = "Run a task")]
     Run { name: String, trailing: Vec<String> },
+    #[clap(
+        name = "resume",
+        about = "Resume the last run of a task or pipeline from its failed step"
+    )]
+    Resume { name: String, trailing: Vec<String> },
 }
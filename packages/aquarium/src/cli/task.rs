@@ -5,9 +5,9 @@ use clap::Parser;
 #[clap(author, about, version)]
 pub struct TaskArgs {
     /// The account to use for this task
-    #[clap(long, short)]
+    #[clap(long, short, global = true)]
     pub account: Option<String>,
     /// The network to use for this task
-    #[clap(long, short)]
+    #[clap(long, short, global = true)]
     pub network: Option<String>,
 }
\ No newline at end of file
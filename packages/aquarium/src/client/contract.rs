@@ -0,0 +1,24 @@
+use crate::ContractInstance;
+
+/// An instance of a contract on chain.
+///
+/// Message enums deriving [`ContractClient`](crate::ContractClient) add a method per variant
+/// to this handle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContractHandle {
+    pub address: String,
+}
+
+impl ContractHandle {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+        }
+    }
+}
+
+impl From<&ContractInstance> for ContractHandle {
+    fn from(instance: &ContractInstance) -> Self {
+        Self::new(instance.address.clone())
+    }
+}
@@ -0,0 +1,503 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use cosmrs::Any;
+use cosmwasm_std::Coin;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    client::messages::AccessConfig, CodeInfo, ContractInfo, Executor, FeeOptions, Querier,
+};
+
+/// Object-safe counterpart of [`Querier`], taking and returning JSON values.
+///
+/// Implemented for every [`Querier`], and `Box<dyn DynQuerier>` implements [`Querier`] in turn.
+#[async_trait]
+pub trait DynQuerier: Send + Sync {
+    async fn dyn_query(&self, address: String, message: Value) -> Result<Value>;
+
+    async fn dyn_wait_for_transaction(&self, tx_hash: String) -> Result<Value>;
+
+    async fn dyn_contract_info(&self, address: String) -> Result<ContractInfo>;
+
+    async fn dyn_code_info(&self, code_id: u64) -> Result<CodeInfo>;
+}
+
+/// Object-safe counterpart of [`Executor`], taking JSON values as messages.
+///
+/// Implemented for every type that is both a [`Querier`] and an [`Executor`], and
+/// `Box<dyn DynExecutor>` implements both in turn.
+#[async_trait]
+pub trait DynExecutor: DynQuerier {
+    async fn dyn_execute_smart(
+        &self,
+        address: String,
+        message: Value,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String>;
+
+    async fn dyn_store_code(&self, bytecode: Vec<u8>, memo: Option<String>) -> Result<String>;
+
+    async fn dyn_store_code_with_access(
+        &self,
+        bytecode: Vec<u8>,
+        access: AccessConfig,
+        memo: Option<String>,
+    ) -> Result<String>;
+
+    async fn dyn_instantiate(
+        &self,
+        code_id: u64,
+        msg: Value,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+    ) -> Result<String>;
+
+    async fn dyn_migrate(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: Value,
+        memo: Option<String>,
+    ) -> Result<String>;
+
+    async fn dyn_update_admin(
+        &self,
+        address: String,
+        new_admin: String,
+        memo: Option<String>,
+    ) -> Result<String>;
+
+    async fn dyn_clear_admin(&self, address: String, memo: Option<String>) -> Result<String>;
+
+    async fn dyn_bank_send(
+        &self,
+        to_address: String,
+        amount: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String>;
+
+    async fn dyn_execute_smart_with_fee(
+        &self,
+        address: String,
+        message: Value,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>;
+
+    async fn dyn_store_code_with_fee(
+        &self,
+        bytecode: Vec<u8>,
+        access: Option<AccessConfig>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn dyn_instantiate_with_fee(
+        &self,
+        code_id: u64,
+        msg: Value,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>;
+
+    async fn dyn_migrate_with_fee(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: Value,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>;
+
+    async fn dyn_execute_any(&self, messages: Vec<Any>, memo: Option<String>) -> Result<String>;
+
+    async fn dyn_execute_any_with_fee(
+        &self,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>;
+
+    fn dyn_address(&self) -> String;
+}
+
+#[async_trait]
+impl<T: Querier + Send + Sync> DynQuerier for T {
+    async fn dyn_query(&self, address: String, message: Value) -> Result<Value> {
+        self.query(address, &message).await
+    }
+
+    async fn dyn_wait_for_transaction(&self, tx_hash: String) -> Result<Value> {
+        self.wait_for_transaction(tx_hash).await
+    }
+
+    async fn dyn_contract_info(&self, address: String) -> Result<ContractInfo> {
+        self.contract_info(address).await
+    }
+
+    async fn dyn_code_info(&self, code_id: u64) -> Result<CodeInfo> {
+        self.code_info(code_id).await
+    }
+}
+
+#[async_trait]
+impl<T: Querier + Executor + Send + Sync> DynExecutor for T {
+    async fn dyn_execute_smart(
+        &self,
+        address: String,
+        message: Value,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.execute_smart(address, &message, funds, memo).await
+    }
+
+    async fn dyn_store_code(&self, bytecode: Vec<u8>, memo: Option<String>) -> Result<String> {
+        self.store_code(bytecode, memo).await
+    }
+
+    async fn dyn_store_code_with_access(
+        &self,
+        bytecode: Vec<u8>,
+        access: AccessConfig,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.store_code_with_access(bytecode, access, memo).await
+    }
+
+    async fn dyn_instantiate(
+        &self,
+        code_id: u64,
+        msg: Value,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.instantiate(code_id, &msg, funds, label, admin, memo)
+            .await
+    }
+
+    async fn dyn_migrate(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: Value,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.migrate(address, code_id, &msg, memo).await
+    }
+
+    async fn dyn_update_admin(
+        &self,
+        address: String,
+        new_admin: String,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.update_admin(address, new_admin, memo).await
+    }
+
+    async fn dyn_clear_admin(&self, address: String, memo: Option<String>) -> Result<String> {
+        self.clear_admin(address, memo).await
+    }
+
+    async fn dyn_bank_send(
+        &self,
+        to_address: String,
+        amount: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.bank_send(to_address, amount, memo).await
+    }
+
+    async fn dyn_execute_smart_with_fee(
+        &self,
+        address: String,
+        message: Value,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        self.execute_smart_with_fee(address, &message, funds, memo, fee)
+            .await
+    }
+
+    async fn dyn_store_code_with_fee(
+        &self,
+        bytecode: Vec<u8>,
+        access: Option<AccessConfig>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        self.store_code_with_fee(bytecode, access, memo, fee).await
+    }
+
+    async fn dyn_instantiate_with_fee(
+        &self,
+        code_id: u64,
+        msg: Value,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        self.instantiate_with_fee(code_id, &msg, funds, label, admin, memo, fee)
+            .await
+    }
+
+    async fn dyn_migrate_with_fee(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: Value,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        self.migrate_with_fee(address, code_id, &msg, memo, fee)
+            .await
+    }
+
+    async fn dyn_execute_any(&self, messages: Vec<Any>, memo: Option<String>) -> Result<String> {
+        self.execute_any(messages, memo).await
+    }
+
+    async fn dyn_execute_any_with_fee(
+        &self,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        self.execute_any_with_fee(messages, memo, fee).await
+    }
+
+    fn dyn_address(&self) -> String {
+        self.address()
+    }
+}
+
+/// Implement [`Querier`] for a boxed trait object by serializing through JSON.
+///
+/// Calls go through `**self`, as the box itself also gets the blanket implementations.
+macro_rules! impl_querier {
+    ($ty:ty) => {
+        #[async_trait]
+        impl Querier for $ty {
+            async fn query<Req, Res>(&self, address: String, message: &Req) -> Result<Res>
+            where
+                Req: Serialize + ?Sized + Sync,
+                Res: for<'de> serde::Deserialize<'de>,
+            {
+                let response = (**self)
+                    .dyn_query(address, serde_json::to_value(message)?)
+                    .await?;
+                Ok(serde_json::from_value(response)?)
+            }
+
+            async fn wait_for_transaction(&self, tx_hash: String) -> Result<Value> {
+                (**self).dyn_wait_for_transaction(tx_hash).await
+            }
+
+            async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+                (**self).dyn_contract_info(address).await
+            }
+
+            async fn code_info(&self, code_id: u64) -> Result<CodeInfo> {
+                (**self).dyn_code_info(code_id).await
+            }
+        }
+    };
+}
+
+impl_querier!(Box<dyn DynQuerier>);
+impl_querier!(Box<dyn DynExecutor>);
+
+#[async_trait]
+impl Executor for Box<dyn DynExecutor> {
+    async fn execute_smart<Req>(
+        &self,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        (**self)
+            .dyn_execute_smart(address, serde_json::to_value(message)?, funds, memo)
+            .await
+    }
+
+    async fn store_code(&self, bytecode: Vec<u8>, memo: Option<String>) -> Result<String> {
+        (**self).dyn_store_code(bytecode, memo).await
+    }
+
+    async fn store_code_with_access(
+        &self,
+        bytecode: Vec<u8>,
+        access: AccessConfig,
+        memo: Option<String>,
+    ) -> Result<String> {
+        (**self)
+            .dyn_store_code_with_access(bytecode, access, memo)
+            .await
+    }
+
+    async fn instantiate<Req>(
+        &self,
+        code_id: u64,
+        msg: &Req,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        (**self)
+            .dyn_instantiate(
+                code_id,
+                serde_json::to_value(msg)?,
+                funds,
+                label,
+                admin,
+                memo,
+            )
+            .await
+    }
+
+    async fn migrate<Req>(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: &Req,
+        memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        (**self)
+            .dyn_migrate(address, code_id, serde_json::to_value(msg)?, memo)
+            .await
+    }
+
+    async fn update_admin(
+        &self,
+        address: String,
+        new_admin: String,
+        memo: Option<String>,
+    ) -> Result<String> {
+        (**self).dyn_update_admin(address, new_admin, memo).await
+    }
+
+    async fn clear_admin(&self, address: String, memo: Option<String>) -> Result<String> {
+        (**self).dyn_clear_admin(address, memo).await
+    }
+
+    async fn bank_send(
+        &self,
+        to_address: String,
+        amount: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        (**self).dyn_bank_send(to_address, amount, memo).await
+    }
+
+    async fn execute_smart_with_fee<Req>(
+        &self,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        (**self)
+            .dyn_execute_smart_with_fee(address, serde_json::to_value(message)?, funds, memo, fee)
+            .await
+    }
+
+    async fn store_code_with_fee(
+        &self,
+        bytecode: Vec<u8>,
+        access: Option<AccessConfig>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        (**self)
+            .dyn_store_code_with_fee(bytecode, access, memo, fee)
+            .await
+    }
+
+    async fn instantiate_with_fee<Req>(
+        &self,
+        code_id: u64,
+        msg: &Req,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        (**self)
+            .dyn_instantiate_with_fee(
+                code_id,
+                serde_json::to_value(msg)?,
+                funds,
+                label,
+                admin,
+                memo,
+                fee,
+            )
+            .await
+    }
+
+    async fn migrate_with_fee<Req>(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: &Req,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        (**self)
+            .dyn_migrate_with_fee(address, code_id, serde_json::to_value(msg)?, memo, fee)
+            .await
+    }
+
+    async fn execute_any(&self, messages: Vec<Any>, memo: Option<String>) -> Result<String> {
+        (**self).dyn_execute_any(messages, memo).await
+    }
+
+    async fn execute_any_with_fee(
+        &self,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        (**self).dyn_execute_any_with_fee(messages, memo, fee).await
+    }
+
+    fn address(&self) -> String {
+        (**self).dyn_address()
+    }
+}
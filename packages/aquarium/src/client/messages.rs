@@ -0,0 +1,720 @@
+//! Constructors for common messages, encoded as protobuf `Any`s so several can be
+//! sent in one transaction with [`Executor::execute_any`](crate::Executor::execute_any).
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cosmrs::{
+    bank::{MsgMultiSend, MsgSend, MultiSendIo},
+    cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract},
+    distribution::MsgWithdrawDelegatorReward,
+    proto,
+    staking::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate},
+    tx::Msg,
+    AccountId, Any,
+};
+use cosmwasm_std::Coin;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::{cosmwasm_coin_to_cosmrs_coin, cosmwasm_coins_to_cosmrs_coins};
+
+pub use proto::cosmos::gov::v1beta1::VoteOption;
+
+fn account_id(address: &str, what: &str) -> Result<AccountId> {
+    AccountId::from_str(address).map_err(|_| anyhow::anyhow!("Invalid {what} address {address}"))
+}
+
+fn to_any(msg: impl Msg) -> Result<Any> {
+    msg.to_any().map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Encode a protobuf message that has no `cosmrs` type.
+fn encode(type_url: &str, msg: &impl prost::Message) -> Any {
+    Any {
+        type_url: type_url.to_string(),
+        value: msg.encode_to_vec(),
+    }
+}
+
+/// Execute a contract with a serialized JSON message.
+pub fn execute(sender: &str, contract: &str, msg: Vec<u8>, funds: Vec<Coin>) -> Result<Any> {
+    to_any(MsgExecuteContract {
+        sender: account_id(sender, "sender")?,
+        contract: account_id(contract, "contract")?,
+        msg,
+        funds: cosmwasm_coins_to_cosmrs_coins(funds),
+    })
+}
+
+/// Migrate a contract administered by `sender` to `code_id` with a serialized JSON message.
+pub fn migrate(sender: &str, contract: &str, code_id: u64, msg: Vec<u8>) -> Result<Any> {
+    to_any(MsgMigrateContract {
+        sender: account_id(sender, "sender")?,
+        contract: account_id(contract, "contract")?,
+        code_id,
+        msg,
+    })
+}
+
+/// Who may instantiate stored code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessConfig {
+    Everybody,
+    Nobody,
+    AnyOfAddresses(Vec<String>),
+}
+
+impl AccessConfig {
+    fn to_proto(&self) -> Result<wasm::AccessConfig> {
+        let (permission, addresses) = match self {
+            Self::Everybody => (wasm::ACCESS_TYPE_EVERYBODY, vec![]),
+            Self::Nobody => (wasm::ACCESS_TYPE_NOBODY, vec![]),
+            Self::AnyOfAddresses(addresses) => {
+                if addresses.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "AnyOfAddresses must list at least one address"
+                    ));
+                }
+                for address in addresses {
+                    account_id(address, "instantiator")?;
+                }
+                (wasm::ACCESS_TYPE_ANY_OF_ADDRESSES, addresses.clone())
+            }
+        };
+        Ok(wasm::AccessConfig {
+            permission,
+            addresses,
+        })
+    }
+
+    fn from_proto(access: wasm::AccessConfig) -> Result<Self> {
+        match access.permission {
+            wasm::ACCESS_TYPE_EVERYBODY => Ok(Self::Everybody),
+            wasm::ACCESS_TYPE_NOBODY => Ok(Self::Nobody),
+            wasm::ACCESS_TYPE_ANY_OF_ADDRESSES => Ok(Self::AnyOfAddresses(access.addresses)),
+            permission => Err(anyhow::anyhow!("Unsupported access type {permission}")),
+        }
+    }
+}
+
+impl std::fmt::Display for AccessConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Everybody => write!(f, "everybody"),
+            Self::Nobody => write!(f, "nobody"),
+            Self::AnyOfAddresses(addresses) => write!(f, "{}", addresses.join(", ")),
+        }
+    }
+}
+
+/// Why code stored with `access` could be instantiated by anyone, if it could. `None` leaves
+/// the permission to the chain, which usually lets everybody instantiate.
+pub fn open_access_warning(access: Option<&AccessConfig>) -> Option<&'static str> {
+    match access {
+        None => Some("the code will be instantiable by everybody unless the chain restricts it, set an instantiate permission"),
+        Some(AccessConfig::Everybody) => Some("the code will be instantiable by everybody"),
+        Some(_) => None,
+    }
+}
+
+/// Upload wasm bytecode, leaving who may instantiate it to the chain's default if `access`
+/// is `None`.
+pub fn store_code(sender: &str, bytecode: Vec<u8>, access: Option<AccessConfig>) -> Result<Any> {
+    account_id(sender, "sender")?;
+    Ok(encode(
+        "/cosmwasm.wasm.v1.MsgStoreCode",
+        &wasm::MsgStoreCode {
+            sender: sender.to_string(),
+            wasm_byte_code: bytecode,
+            instantiate_permission: access.as_ref().map(AccessConfig::to_proto).transpose()?,
+        },
+    ))
+}
+
+/// The bytecode and instantiate permission of an encoded `MsgStoreCode`.
+pub(crate) fn decode_store_code(msg: &Any) -> Result<(Vec<u8>, Option<AccessConfig>)> {
+    let msg = wasm::MsgStoreCode::decode(msg.value.as_slice())?;
+    let access = msg
+        .instantiate_permission
+        .map(AccessConfig::from_proto)
+        .transpose()?;
+    Ok((msg.wasm_byte_code, access))
+}
+
+/// Change who may instantiate code uploaded by `sender`.
+pub fn update_instantiate_config(sender: &str, code_id: u64, access: AccessConfig) -> Result<Any> {
+    account_id(sender, "sender")?;
+    Ok(encode(
+        "/cosmwasm.wasm.v1.MsgUpdateInstantiateConfig",
+        &wasm::MsgUpdateInstantiateConfig {
+            sender: sender.to_string(),
+            code_id,
+            new_instantiate_permission: Some(access.to_proto()?),
+        },
+    ))
+}
+
+/// Instantiate a contract with a serialized JSON message.
+pub fn instantiate(
+    sender: &str,
+    code_id: u64,
+    msg: Vec<u8>,
+    funds: Vec<Coin>,
+    label: Option<String>,
+    admin: Option<String>,
+) -> Result<Any> {
+    to_any(MsgInstantiateContract {
+        sender: account_id(sender, "sender")?,
+        admin: admin.map(|admin| account_id(&admin, "admin")).transpose()?,
+        code_id,
+        label,
+        msg,
+        funds: cosmwasm_coins_to_cosmrs_coins(funds),
+    })
+}
+
+/// Instantiate a contract at the address [`predict_address`](crate::utils::predict_address)
+/// gives for `sender` and `salt`, or [`predict_address_with_msg`](crate::utils::predict_address_with_msg)
+/// with `fix_msg`.
+#[allow(clippy::too_many_arguments)]
+pub fn instantiate2(
+    sender: &str,
+    code_id: u64,
+    msg: Vec<u8>,
+    funds: Vec<Coin>,
+    label: Option<String>,
+    admin: Option<String>,
+    salt: Vec<u8>,
+    fix_msg: bool,
+) -> Result<Any> {
+    account_id(sender, "sender")?;
+    if let Some(admin) = &admin {
+        account_id(admin, "admin")?;
+    }
+    if salt.is_empty() || salt.len() > 64 {
+        return Err(anyhow::anyhow!("The salt must be 1 to 64 bytes long"));
+    }
+    Ok(encode(
+        "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+        &proto::cosmwasm::wasm::v1::MsgInstantiateContract2 {
+            sender: sender.to_string(),
+            admin: admin.unwrap_or_default(),
+            code_id,
+            label: label.unwrap_or_default(),
+            msg,
+            funds: funds
+                .into_iter()
+                .map(|coin| proto::cosmos::base::v1beta1::Coin {
+                    denom: coin.denom,
+                    amount: coin.amount.to_string(),
+                })
+                .collect(),
+            salt,
+            fix_msg,
+        },
+    ))
+}
+
+/// Make `new_admin` the admin of a contract administered by `sender`.
+pub fn update_admin(sender: &str, contract: &str, new_admin: &str) -> Result<Any> {
+    account_id(sender, "sender")?;
+    account_id(contract, "contract")?;
+    account_id(new_admin, "admin")?;
+    Ok(encode(
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin",
+        &proto::cosmwasm::wasm::v1::MsgUpdateAdmin {
+            sender: sender.to_string(),
+            new_admin: new_admin.to_string(),
+            contract: contract.to_string(),
+        },
+    ))
+}
+
+/// Remove the admin of a contract administered by `sender`, so it can't be migrated again.
+pub fn clear_admin(sender: &str, contract: &str) -> Result<Any> {
+    account_id(sender, "sender")?;
+    account_id(contract, "contract")?;
+    Ok(encode(
+        "/cosmwasm.wasm.v1.MsgClearAdmin",
+        &proto::cosmwasm::wasm::v1::MsgClearAdmin {
+            sender: sender.to_string(),
+            contract: contract.to_string(),
+        },
+    ))
+}
+
+/// The amino JSON of a wasm message, as signed in `SIGN_MODE_LEGACY_AMINO_JSON`. Other messages
+/// have no amino registration here and must be signed in direct mode.
+pub fn to_amino_json(msg: &Any) -> Result<Value> {
+    use proto::cosmwasm::wasm::v1 as wasm_v1;
+
+    let bytes = msg.value.as_slice();
+    let (amino_type, value) = match msg.type_url.as_str() {
+        "/cosmwasm.wasm.v1.MsgStoreCode" => {
+            let msg = wasm::MsgStoreCode::decode(bytes)?;
+            (
+                "wasm/MsgStoreCode",
+                json!({
+                    "sender": msg.sender,
+                    "wasm_byte_code": STANDARD.encode(msg.wasm_byte_code),
+                    "instantiate_permission": msg.instantiate_permission.map(amino_access),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgUpdateInstantiateConfig" => {
+            let msg = wasm::MsgUpdateInstantiateConfig::decode(bytes)?;
+            (
+                "wasm/MsgUpdateInstantiateConfig",
+                json!({
+                    "sender": msg.sender,
+                    "code_id": msg.code_id.to_string(),
+                    "new_instantiate_permission": msg.new_instantiate_permission.map(amino_access),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+            let msg = wasm_v1::MsgInstantiateContract::decode(bytes)?;
+            (
+                "wasm/MsgInstantiateContract",
+                json!({
+                    "sender": msg.sender,
+                    "admin": msg.admin,
+                    "code_id": msg.code_id.to_string(),
+                    "label": msg.label,
+                    "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+                    "funds": amino_coins(&msg.funds),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgInstantiateContract2" => {
+            let msg = wasm_v1::MsgInstantiateContract2::decode(bytes)?;
+            (
+                "wasm/MsgInstantiateContract2",
+                json!({
+                    "sender": msg.sender,
+                    "admin": msg.admin,
+                    "code_id": msg.code_id.to_string(),
+                    "label": msg.label,
+                    "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+                    "funds": amino_coins(&msg.funds),
+                    "salt": STANDARD.encode(msg.salt),
+                    "fix_msg": msg.fix_msg,
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+            let msg = wasm_v1::MsgExecuteContract::decode(bytes)?;
+            (
+                "wasm/MsgExecuteContract",
+                json!({
+                    "sender": msg.sender,
+                    "contract": msg.contract,
+                    "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+                    "funds": amino_coins(&msg.funds),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+            let msg = wasm_v1::MsgMigrateContract::decode(bytes)?;
+            (
+                "wasm/MsgMigrateContract",
+                json!({
+                    "sender": msg.sender,
+                    "contract": msg.contract,
+                    "code_id": msg.code_id.to_string(),
+                    "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+            let msg = wasm_v1::MsgUpdateAdmin::decode(bytes)?;
+            (
+                "wasm/MsgUpdateAdmin",
+                json!({
+                    "sender": msg.sender,
+                    "new_admin": msg.new_admin,
+                    "contract": msg.contract,
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgClearAdmin" => {
+            let msg = wasm_v1::MsgClearAdmin::decode(bytes)?;
+            (
+                "wasm/MsgClearAdmin",
+                json!({ "sender": msg.sender, "contract": msg.contract }),
+            )
+        }
+        type_url => {
+            return Err(anyhow::anyhow!(
+                "{type_url} has no amino JSON registration, sign it in direct mode"
+            ))
+        }
+    };
+    Ok(json!({ "type": amino_type, "value": omit_empty(value) }))
+}
+
+/// Amino leaves out empty strings, false and unset messages, but always lists funds.
+fn omit_empty(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| {
+                    !matches!(value, Value::Null | Value::Bool(false)) && value.as_str() != Some("")
+                })
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn amino_coins(coins: &[proto::cosmos::base::v1beta1::Coin]) -> Vec<Value> {
+    coins
+        .iter()
+        .map(|coin| json!({ "denom": coin.denom, "amount": coin.amount }))
+        .collect()
+}
+
+/// Wasmd marshals access types by name rather than as the protobuf enum.
+fn amino_access(access: wasm::AccessConfig) -> Value {
+    let permission = match access.permission {
+        wasm::ACCESS_TYPE_NOBODY => "Nobody",
+        wasm::ACCESS_TYPE_EVERYBODY => "Everybody",
+        wasm::ACCESS_TYPE_ANY_OF_ADDRESSES => "AnyOfAddresses",
+        _ => "Unspecified",
+    };
+    let mut value = json!({ "permission": permission });
+    if !access.addresses.is_empty() {
+        value["addresses"] = json!(access.addresses);
+    }
+    value
+}
+
+/// Type URL of a contract execution, the message authz grants default to.
+pub const EXECUTE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+
+/// Send `msgs` on behalf of the accounts that granted `grantee` permission through authz.
+/// The signer of each message is its granter.
+pub fn exec(grantee: &str, msgs: Vec<Any>) -> Result<Any> {
+    account_id(grantee, "grantee")?;
+    Ok(encode(
+        "/cosmos.authz.v1beta1.MsgExec",
+        &authz::MsgExec {
+            grantee: grantee.to_string(),
+            msgs,
+        },
+    ))
+}
+
+/// Let `grantee` send messages of type `msg_type_url` on behalf of `granter`, until
+/// `expiration` in seconds since the Unix epoch if given.
+pub fn grant(
+    granter: &str,
+    grantee: &str,
+    msg_type_url: &str,
+    expiration: Option<u64>,
+) -> Result<Any> {
+    account_id(granter, "granter")?;
+    account_id(grantee, "grantee")?;
+    Ok(encode(
+        "/cosmos.authz.v1beta1.MsgGrant",
+        &authz::MsgGrant {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            grant: Some(authz::Grant {
+                authorization: Some(encode(
+                    "/cosmos.authz.v1beta1.GenericAuthorization",
+                    &authz::GenericAuthorization {
+                        msg: msg_type_url.to_string(),
+                    },
+                )),
+                expiration: expiration.map(|seconds| authz::Timestamp {
+                    seconds: seconds as i64,
+                    nanos: 0,
+                }),
+            }),
+        },
+    ))
+}
+
+/// Withdraw the permission of `grantee` to send messages of type `msg_type_url` on behalf
+/// of `granter`.
+pub fn revoke(granter: &str, grantee: &str, msg_type_url: &str) -> Result<Any> {
+    account_id(granter, "granter")?;
+    account_id(grantee, "grantee")?;
+    Ok(encode(
+        "/cosmos.authz.v1beta1.MsgRevoke",
+        &authz::MsgRevoke {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            msg_type_url: msg_type_url.to_string(),
+        },
+    ))
+}
+
+pub fn send(from_address: &str, to_address: &str, amount: Vec<Coin>) -> Result<Any> {
+    to_any(MsgSend {
+        from_address: account_id(from_address, "sender")?,
+        to_address: account_id(to_address, "recipient")?,
+        amount: cosmwasm_coins_to_cosmrs_coins(amount),
+    })
+}
+
+/// Send to several recipients at once, with a single input from `from_address`.
+pub fn multi_send(from_address: &str, outputs: Vec<(String, Vec<Coin>)>) -> Result<Any> {
+    let mut total = BTreeMap::<String, u128>::new();
+    for coin in outputs.iter().flat_map(|(_, coins)| coins) {
+        *total.entry(coin.denom.clone()).or_default() += coin.amount.u128();
+    }
+    let input = MultiSendIo {
+        address: account_id(from_address, "sender")?,
+        coins: cosmwasm_coins_to_cosmrs_coins(
+            total
+                .into_iter()
+                .map(|(denom, amount)| cosmwasm_std::coin(amount, denom))
+                .collect(),
+        ),
+    };
+    let outputs = outputs
+        .into_iter()
+        .map(|(address, coins)| {
+            Ok(MultiSendIo {
+                address: account_id(&address, "recipient")?,
+                coins: cosmwasm_coins_to_cosmrs_coins(coins),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    to_any(MsgMultiSend {
+        inputs: vec![input],
+        outputs,
+    })
+}
+
+pub fn delegate(delegator_address: &str, validator_address: &str, amount: Coin) -> Result<Any> {
+    to_any(MsgDelegate {
+        delegator_address: account_id(delegator_address, "delegator")?,
+        validator_address: account_id(validator_address, "validator")?,
+        amount: cosmwasm_coin_to_cosmrs_coin(amount),
+    })
+}
+
+pub fn undelegate(delegator_address: &str, validator_address: &str, amount: Coin) -> Result<Any> {
+    to_any(MsgUndelegate {
+        delegator_address: account_id(delegator_address, "delegator")?,
+        validator_address: account_id(validator_address, "validator")?,
+        amount: cosmwasm_coin_to_cosmrs_coin(amount),
+    })
+}
+
+pub fn redelegate(
+    delegator_address: &str,
+    src_validator_address: &str,
+    dst_validator_address: &str,
+    amount: Coin,
+) -> Result<Any> {
+    to_any(MsgBeginRedelegate {
+        delegator_address: account_id(delegator_address, "delegator")?,
+        validator_src_address: account_id(src_validator_address, "validator")?,
+        validator_dst_address: account_id(dst_validator_address, "validator")?,
+        amount: cosmwasm_coin_to_cosmrs_coin(amount),
+    })
+}
+
+pub fn withdraw_rewards(delegator_address: &str, validator_address: &str) -> Result<Any> {
+    to_any(MsgWithdrawDelegatorReward {
+        delegator_address: account_id(delegator_address, "delegator")?,
+        validator_address: account_id(validator_address, "validator")?,
+    })
+}
+
+pub fn vote(voter: &str, proposal_id: u64, option: VoteOption) -> Result<Any> {
+    account_id(voter, "voter")?;
+    Ok(encode(
+        "/cosmos.gov.v1beta1.MsgVote",
+        &proto::cosmos::gov::v1beta1::MsgVote {
+            proposal_id,
+            voter: voter.to_string(),
+            option: option as i32,
+        },
+    ))
+}
+
+/// The denom created by [`create_denom`], `factory/{creator}/{nonce}`.
+pub fn factory_denom(creator: &str, nonce: &str) -> String {
+    format!("factory/{creator}/{nonce}")
+}
+
+/// Create the Kujira tokenfactory denom [`factory_denom`]`(sender, nonce)`. The chain charges
+/// a creation fee.
+pub fn create_denom(sender: &str, nonce: &str) -> Result<Any> {
+    account_id(sender, "sender")?;
+    Ok(encode(
+        "/kujira.denom.MsgCreateDenom",
+        &kujira::MsgCreateDenom {
+            sender: sender.to_string(),
+            nonce: nonce.to_string(),
+        },
+    ))
+}
+
+/// Mint a tokenfactory denom administered by `sender` to `recipient`.
+pub fn mint(sender: &str, amount: Coin, recipient: &str) -> Result<Any> {
+    account_id(sender, "sender")?;
+    account_id(recipient, "recipient")?;
+    Ok(encode(
+        "/kujira.denom.MsgMint",
+        &kujira::MsgMint {
+            sender: sender.to_string(),
+            amount: Some(kujira::coin(amount)),
+            recipient: recipient.to_string(),
+        },
+    ))
+}
+
+/// Burn a tokenfactory denom from the balance of `sender`, who must administer it.
+pub fn burn(sender: &str, amount: Coin) -> Result<Any> {
+    account_id(sender, "sender")?;
+    Ok(encode(
+        "/kujira.denom.MsgBurn",
+        &kujira::MsgBurn {
+            sender: sender.to_string(),
+            amount: Some(kujira::coin(amount)),
+        },
+    ))
+}
+
+/// Wasm messages with an `AccessConfig` that can list several addresses, which the `cosmrs`
+/// types predate.
+mod wasm {
+    use prost::Message;
+
+    pub const ACCESS_TYPE_NOBODY: i32 = 1;
+    pub const ACCESS_TYPE_EVERYBODY: i32 = 3;
+    pub const ACCESS_TYPE_ANY_OF_ADDRESSES: i32 = 4;
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct AccessConfig {
+        #[prost(int32, tag = "1")]
+        pub permission: i32,
+        #[prost(string, repeated, tag = "3")]
+        pub addresses: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgStoreCode {
+        #[prost(string, tag = "1")]
+        pub sender: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub wasm_byte_code: Vec<u8>,
+        #[prost(message, optional, tag = "5")]
+        pub instantiate_permission: Option<AccessConfig>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgUpdateInstantiateConfig {
+        #[prost(string, tag = "1")]
+        pub sender: String,
+        #[prost(uint64, tag = "2")]
+        pub code_id: u64,
+        #[prost(message, optional, tag = "3")]
+        pub new_instantiate_permission: Option<AccessConfig>,
+    }
+}
+
+/// Messages of the authz module, with a generic authorization.
+mod authz {
+    use cosmrs::Any;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgExec {
+        #[prost(string, tag = "1")]
+        pub grantee: String,
+        #[prost(message, repeated, tag = "2")]
+        pub msgs: Vec<Any>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgGrant {
+        #[prost(string, tag = "1")]
+        pub granter: String,
+        #[prost(string, tag = "2")]
+        pub grantee: String,
+        #[prost(message, optional, tag = "3")]
+        pub grant: Option<Grant>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct Grant {
+        #[prost(message, optional, tag = "1")]
+        pub authorization: Option<Any>,
+        #[prost(message, optional, tag = "2")]
+        pub expiration: Option<Timestamp>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct GenericAuthorization {
+        #[prost(string, tag = "1")]
+        pub msg: String,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct Timestamp {
+        #[prost(int64, tag = "1")]
+        pub seconds: i64,
+        #[prost(int32, tag = "2")]
+        pub nanos: i32,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgRevoke {
+        #[prost(string, tag = "1")]
+        pub granter: String,
+        #[prost(string, tag = "2")]
+        pub grantee: String,
+        #[prost(string, tag = "3")]
+        pub msg_type_url: String,
+    }
+}
+
+/// Messages of Kujira's `x/denom` module, which `cosmrs` doesn't include.
+mod kujira {
+    use cosmrs::proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgCreateDenom {
+        #[prost(string, tag = "1")]
+        pub sender: String,
+        #[prost(string, tag = "2")]
+        pub nonce: String,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgMint {
+        #[prost(string, tag = "1")]
+        pub sender: String,
+        #[prost(message, optional, tag = "2")]
+        pub amount: Option<ProtoCoin>,
+        #[prost(string, tag = "3")]
+        pub recipient: String,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgBurn {
+        #[prost(string, tag = "1")]
+        pub sender: String,
+        #[prost(message, optional, tag = "2")]
+        pub amount: Option<ProtoCoin>,
+    }
+
+    pub fn coin(coin: cosmwasm_std::Coin) -> ProtoCoin {
+        ProtoCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        }
+    }
+}
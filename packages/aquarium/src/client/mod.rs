@@ -1,3 +1,9 @@
+pub mod contract;
+pub mod dynamic;
+pub mod messages;
+pub mod offline;
 pub mod query;
+pub mod signer;
 pub mod signing;
-pub mod utils;
\ No newline at end of file
+pub mod transport;
+pub mod utils;
@@ -0,0 +1,432 @@
+//! Transactions that are signed away from the machine that builds them, such as by the
+//! members of a multisig, then broadcast once every signature is collected.
+
+use std::{collections::BTreeMap, path::Path, str::FromStr};
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cosmrs::{
+    crypto::PublicKey,
+    proto::cosmos::{
+        crypto::multisig::v1beta1::{CompactBitArray, MultiSignature},
+        tx::{
+            signing::v1beta1::SignMode as ProtoSignMode,
+            v1beta1::{
+                mode_info::{self, Sum},
+                AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw,
+            },
+        },
+    },
+    AccountId, Any,
+};
+use cosmwasm_std::Binary;
+use k256::ecdsa::VerifyingKey;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    client::{messages::to_amino_json, signer::Signer},
+    Network,
+};
+
+/// How signers sign a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignMode {
+    /// The protobuf encoding of the transaction, `SIGN_MODE_DIRECT`.
+    #[default]
+    Direct,
+    /// A JSON document of the transaction, `SIGN_MODE_LEGACY_AMINO_JSON`, for ledgers and
+    /// tools that can't sign protobuf. Only messages with an amino registration can be signed.
+    AminoJson,
+}
+
+impl FromStr for SignMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "direct" => Ok(Self::Direct),
+            "amino_json" => Ok(Self::AminoJson),
+            _ => Err(anyhow::anyhow!(
+                "Unknown sign mode {s}, expected direct or amino_json"
+            )),
+        }
+    }
+}
+
+/// A legacy amino multisig public key, `threshold` of `public_keys`.
+#[derive(Debug, Clone)]
+pub struct Multisig {
+    pub threshold: u32,
+    /// The members, in the order the multisig was created with.
+    pub public_keys: Vec<PublicKey>,
+}
+
+impl Multisig {
+    /// The address of the multisig, the truncated hash of its amino encoding.
+    pub fn address(&self, prefix: &str) -> Result<String> {
+        let mut amino = vec![0x22, 0xc1, 0xf7, 0xe2];
+        amino.push(0x08);
+        prost::encoding::encode_varint(self.threshold.into(), &mut amino);
+        for public_key in &self.public_keys {
+            let key = public_key.to_bytes();
+            amino.push(0x12);
+            prost::encoding::encode_varint(key.len() as u64 + 5, &mut amino);
+            amino.extend_from_slice(&[0xeb, 0x5a, 0xe9, 0x87]);
+            amino.push(key.len() as u8);
+            amino.extend_from_slice(&key);
+        }
+        let hash = Sha256::digest(amino);
+        Ok(AccountId::new(prefix, &hash[..20])
+            .map_err(|e| anyhow::anyhow!(e))?
+            .to_string())
+    }
+
+    fn to_any(&self) -> Any {
+        Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+            value: LegacyAminoPubKey {
+                threshold: self.threshold,
+                public_keys: self.public_keys.iter().map(public_key_any).collect(),
+            }
+            .encode_to_vec(),
+        }
+    }
+}
+
+/// Who signs an [`OfflineTx`].
+#[derive(Debug, Clone)]
+pub enum TxSigner {
+    Single(PublicKey),
+    /// The members of a multisig in `signers` sign. Direct signatures cover the set of
+    /// signers, so it is chosen before anyone signs.
+    Multisig {
+        multisig: Multisig,
+        signers: Vec<PublicKey>,
+    },
+}
+
+/// An unsigned or partially signed transaction, as written by `aq tx ... --generate-only`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfflineTx {
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    /// The address sending the transaction.
+    pub address: String,
+    pub body_bytes: Binary,
+    pub auth_info_bytes: Binary,
+    /// Public keys of the expected signers, in multisig order.
+    pub signers: Vec<Binary>,
+    /// Whether the signatures combine into a multisig signature.
+    #[serde(default)]
+    pub multisig: bool,
+    /// Signatures by the base64 public key that made them.
+    #[serde(default)]
+    pub signatures: BTreeMap<String, Binary>,
+    /// The document signers sign in amino JSON mode, or `None` in direct mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amino_sign_doc: Option<String>,
+}
+
+impl OfflineTx {
+    /// An unsigned transaction of `messages`, valid until `timeout_height` if it isn't 0.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        chain_id: &str,
+        address: &str,
+        account_number: u64,
+        sequence: u64,
+        signer: &TxSigner,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        timeout_height: u64,
+        fee: Fee,
+        sign_mode: SignMode,
+    ) -> Result<Self> {
+        let memo = memo.unwrap_or_default();
+        let amino_sign_doc = match sign_mode {
+            SignMode::Direct => None,
+            SignMode::AminoJson => Some(amino_sign_doc(
+                chain_id,
+                account_number,
+                sequence,
+                &fee,
+                &messages,
+                &memo,
+                timeout_height,
+            )?),
+        };
+        let single = || ModeInfo {
+            sum: Some(Sum::Single(mode_info::Single {
+                mode: match sign_mode {
+                    SignMode::Direct => ProtoSignMode::Direct,
+                    SignMode::AminoJson => ProtoSignMode::LegacyAminoJson,
+                } as i32,
+            })),
+        };
+        let (public_key, mode_info, signers) = match signer {
+            TxSigner::Single(public_key) => {
+                (public_key_any(public_key), single(), vec![*public_key])
+            }
+            TxSigner::Multisig { multisig, signers } => {
+                let bits = multisig
+                    .public_keys
+                    .iter()
+                    .map(|key| signers.contains(key))
+                    .collect::<Vec<_>>();
+                let mode_info = ModeInfo {
+                    sum: Some(Sum::Multi(mode_info::Multi {
+                        bitarray: Some(bit_array(&bits)),
+                        mode_infos: bits.iter().filter(|bit| **bit).map(|_| single()).collect(),
+                    })),
+                };
+                // Signatures are combined in the order of the multisig's keys.
+                let signers = multisig
+                    .public_keys
+                    .iter()
+                    .filter(|key| signers.contains(key))
+                    .copied()
+                    .collect();
+                (multisig.to_any(), mode_info, signers)
+            }
+        };
+        let body = TxBody {
+            messages,
+            memo,
+            timeout_height,
+            extension_options: vec![],
+            non_critical_extension_options: vec![],
+        };
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: Some(public_key),
+                mode_info: Some(mode_info),
+                sequence,
+            }],
+            fee: Some(fee),
+        };
+        Ok(Self {
+            chain_id: chain_id.to_string(),
+            account_number,
+            sequence,
+            address: address.to_string(),
+            body_bytes: Binary(body.encode_to_vec()),
+            auth_info_bytes: Binary(auth_info.encode_to_vec()),
+            signers: signers
+                .iter()
+                .map(|key: &PublicKey| Binary(key.to_bytes()))
+                .collect(),
+            multisig: matches!(signer, TxSigner::Multisig { .. }),
+            signatures: BTreeMap::new(),
+            amino_sign_doc,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Could not read transaction {}: {e}", path.display()))?;
+        serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    /// The bytes every signer signs.
+    pub fn sign_bytes(&self) -> Vec<u8> {
+        if let Some(doc) = &self.amino_sign_doc {
+            return doc.as_bytes().to_vec();
+        }
+        SignDoc {
+            body_bytes: self.body_bytes.to_vec(),
+            auth_info_bytes: self.auth_info_bytes.to_vec(),
+            chain_id: self.chain_id.clone(),
+            account_number: self.account_number,
+        }
+        .encode_to_vec()
+    }
+
+    /// Add the signature of `signer`, which must be one of the expected signers.
+    pub async fn sign(&mut self, signer: &dyn Signer) -> Result<()> {
+        let public_key = Binary(signer.public_key().to_bytes());
+        if !self.signers.contains(&public_key) {
+            return Err(anyhow::anyhow!(
+                "{} is not one of the signers of this transaction",
+                public_key.to_base64()
+            ));
+        }
+        let signature = signer.sign(&self.sign_bytes()).await?;
+        self.signatures
+            .insert(public_key.to_base64(), Binary(signature));
+        Ok(())
+    }
+
+    /// Add the signatures of another copy of the same transaction.
+    pub fn merge(&mut self, other: OfflineTx) -> Result<()> {
+        if other.chain_id != self.chain_id
+            || other.account_number != self.account_number
+            || other.sequence != self.sequence
+            || other.address != self.address
+            || other.body_bytes != self.body_bytes
+            || other.auth_info_bytes != self.auth_info_bytes
+            || other.signers != self.signers
+            || other.multisig != self.multisig
+            || other.amino_sign_doc != self.amino_sign_doc
+        {
+            return Err(anyhow::anyhow!(
+                "Can't combine signatures of different transactions"
+            ));
+        }
+        self.signatures.extend(other.signatures);
+        Ok(())
+    }
+
+    /// Base64 public keys of the signers that haven't signed yet.
+    pub fn missing_signatures(&self) -> Vec<String> {
+        self.signers
+            .iter()
+            .map(Binary::to_base64)
+            .filter(|key| !self.signatures.contains_key(key))
+            .collect()
+    }
+
+    /// The signed transaction, once every signer has signed.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let missing = self.missing_signatures();
+        if !missing.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Missing signatures from {}",
+                missing.join(", ")
+            ));
+        }
+        let mut signatures = self
+            .signers
+            .iter()
+            .map(|key| self.signatures[&key.to_base64()].to_vec())
+            .collect::<Vec<_>>();
+        if self.multisig {
+            signatures = vec![MultiSignature { signatures }.encode_to_vec()];
+        }
+        Ok(TxRaw {
+            body_bytes: self.body_bytes.to_vec(),
+            auth_info_bytes: self.auth_info_bytes.to_vec(),
+            signatures,
+        }
+        .encode_to_vec())
+    }
+
+    /// Broadcast the signed transaction, returning its hash.
+    pub async fn broadcast(&self, network: &Network) -> Result<String> {
+        network.broadcast(&self.to_bytes()?).await
+    }
+}
+
+/// The amino JSON sign doc of a transaction: compact, with sorted keys and HTML characters
+/// escaped, as the Cosmos SDK marshals it.
+pub fn amino_sign_doc(
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    fee: &Fee,
+    messages: &[Any],
+    memo: &str,
+    timeout_height: u64,
+) -> Result<String> {
+    let mut amino_fee = json!({
+        "amount": fee
+            .amount
+            .iter()
+            .map(|coin| json!({ "amount": coin.amount, "denom": coin.denom }))
+            .collect::<Vec<_>>(),
+        "gas": fee.gas_limit.to_string(),
+    });
+    if !fee.payer.is_empty() {
+        amino_fee["payer"] = json!(fee.payer);
+    }
+    if !fee.granter.is_empty() {
+        amino_fee["granter"] = json!(fee.granter);
+    }
+    let mut doc = json!({
+        "account_number": account_number.to_string(),
+        "chain_id": chain_id,
+        "fee": amino_fee,
+        "memo": memo,
+        "msgs": messages.iter().map(to_amino_json).collect::<Result<Vec<_>>>()?,
+        "sequence": sequence.to_string(),
+    });
+    if timeout_height != 0 {
+        doc["timeout_height"] = json!(timeout_height.to_string());
+    }
+    Ok(serde_json::to_string(&sorted(doc))?
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026"))
+}
+
+/// `value` with the keys of every object sorted, whatever order `serde_json` keeps them in.
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sorted(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        value => value,
+    }
+}
+
+/// Parse a secp256k1 public key, base64 encoded as in `aq tx` files and multisig configs.
+pub fn parse_public_key(key: &str) -> Result<PublicKey> {
+    let bytes = STANDARD
+        .decode(key)
+        .map_err(|_| anyhow::anyhow!("Invalid base64 public key {key}"))?;
+    let key = VerifyingKey::from_sec1_bytes(&bytes)
+        .map_err(|_| anyhow::anyhow!("Invalid secp256k1 public key {key}"))?;
+    Ok(PublicKey::from(key))
+}
+
+fn public_key_any(public_key: &PublicKey) -> Any {
+    Any {
+        type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+        value: Secp256k1PubKey {
+            key: public_key.to_bytes(),
+        }
+        .encode_to_vec(),
+    }
+}
+
+/// Pack bits most significant first, as the Cosmos SDK does.
+fn bit_array(bits: &[bool]) -> CompactBitArray {
+    let mut elems = vec![0u8; bits.len().div_ceil(8)];
+    for (index, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        elems[index / 8] |= 1 << (7 - index % 8);
+    }
+    CompactBitArray {
+        extra_bits_stored: (bits.len() % 8) as u32,
+        elems,
+    }
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Secp256k1PubKey {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct LegacyAminoPubKey {
+    #[prost(uint32, tag = "1")]
+    threshold: u32,
+    #[prost(message, repeated, tag = "2")]
+    public_keys: Vec<Any>,
+}
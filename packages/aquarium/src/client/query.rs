@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use anyhow::Result;
 use serde_json::Value;
@@ -17,6 +17,58 @@ pub trait Querier {
 
     /// Wait for a transaction to be committed.
     async fn wait_for_transaction(&self, tx_hash: String) -> Result<Value>;
+
+    /// Query the on-chain metadata of a contract.
+    async fn contract_info(&self, address: String) -> Result<ContractInfo>;
+
+    /// Query the on-chain metadata of stored code.
+    async fn code_info(&self, code_id: u64) -> Result<CodeInfo>;
+}
+
+/// Contract metadata, as reported by `cosmwasm/wasm/v1/contract/{address}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractInfo {
+    pub address: String,
+    pub code_id: u64,
+    pub creator: String,
+    pub admin: Option<String>,
+    pub label: String,
+}
+
+/// The error of [`Querier::contract_info`] when there is no contract at the address, as
+/// opposed to the query failing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractNotFound(pub String);
+
+impl std::fmt::Display for ContractNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No contract found at {}", self.0)
+    }
+}
+
+impl std::error::Error for ContractNotFound {}
+
+/// Code metadata, as reported by `cosmwasm/wasm/v1/code/{code_id}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeInfo {
+    pub code_id: u64,
+    pub creator: String,
+    /// Lowercase hex encoded SHA-256 checksum of the code.
+    pub checksum: String,
+}
+
+fn parse_u64(value: &Value, field: &str, res: &Value) -> Result<u64> {
+    Ok(value
+        .as_str()
+        .ok_or(anyhow::anyhow!("Error parsing {field}. Response: {res:#}"))?
+        .parse::<u64>()?)
+}
+
+fn parse_string(value: &Value, field: &str, res: &Value) -> Result<String> {
+    Ok(value
+        .as_str()
+        .ok_or(anyhow::anyhow!("Error parsing {field}. Response: {res:#}"))?
+        .to_string())
 }
 pub struct QueryClient {
     network: Network,
@@ -56,7 +108,7 @@ impl Querier for QueryClient {
         loop {
             let res = self.network.get(path.clone()).await?;
             // Check if not found, and if so, sleep and try again.
-            if res["code"].as_u64().map_or(false, |c| c == 5) {
+            if res["code"].as_u64() == Some(5) {
                 tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                 continue;
             }
@@ -64,4 +116,42 @@ impl Querier for QueryClient {
             return Ok(res);
         }
     }
+
+    async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        let res = self
+            .network
+            .get(format!("cosmwasm/wasm/v1/contract/{address}"))
+            .await?;
+        let not_found = res["code"].as_u64() == Some(5)
+            || res["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("not found"));
+        if res["code"].is_u64() && not_found {
+            return Err(ContractNotFound(address).into());
+        }
+        let info = &res["contract_info"];
+        Ok(ContractInfo {
+            address,
+            code_id: parse_u64(&info["code_id"], "code id", &res)?,
+            creator: parse_string(&info["creator"], "creator", &res)?,
+            admin: info["admin"]
+                .as_str()
+                .filter(|admin| !admin.is_empty())
+                .map(|admin| admin.to_string()),
+            label: parse_string(&info["label"], "label", &res)?,
+        })
+    }
+
+    async fn code_info(&self, code_id: u64) -> Result<CodeInfo> {
+        let res = self
+            .network
+            .get(format!("cosmwasm/wasm/v1/code/{code_id}"))
+            .await?;
+        let info = &res["code_info"];
+        Ok(CodeInfo {
+            code_id: parse_u64(&info["code_id"], "code id", &res)?,
+            creator: parse_string(&info["creator"], "creator", &res)?,
+            checksum: parse_string(&info["data_hash"], "checksum", &res)?.to_lowercase(),
+        })
+    }
 }
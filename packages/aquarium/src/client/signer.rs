@@ -0,0 +1,288 @@
+//! Where the keys signing transactions live. [`SigningClient`](crate::SigningClient) only sees
+//! a [`Signer`], so keys can stay in another process or on another machine.
+
+use std::{
+    fmt,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use cosmrs::{
+    bip32::{Mnemonic, XPrv},
+    crypto::PublicKey,
+};
+use cosmwasm_std::Binary;
+use k256::ecdsa::{
+    signature::{Signer as _, Verifier},
+    Signature, SigningKey, VerifyingKey,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::transport::{HttpTransport, Transport};
+
+/// Signs transactions for one secp256k1 public key.
+#[async_trait]
+pub trait Signer: Send + Sync + fmt::Debug {
+    fn public_key(&self) -> PublicKey;
+
+    /// The 64 byte compact signature of the sha256 of `bytes`.
+    async fn sign(&self, bytes: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Signs in-process with a key from a mnemonic or a keystore file.
+pub struct LocalSigner {
+    key: SigningKey,
+}
+
+impl LocalSigner {
+    /// Derive the key at `derivation_path` from `mnemonic`, once.
+    pub fn from_mnemonic(mnemonic: &str, derivation_path: &str) -> Result<Self> {
+        let seed = Mnemonic::new(mnemonic, Default::default())?.to_seed("");
+        let key = XPrv::derive_from_path(seed, &derivation_path.parse()?)?;
+        Ok(Self {
+            key: key.private_key().clone(),
+        })
+    }
+
+    /// Read the hex private key in `path`, as written by `kujirad keys export --unarmored-hex`.
+    /// On unix the file must not be readable by other users.
+    pub fn from_keystore(path: &Path) -> Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(path)
+                .map_err(|e| anyhow::anyhow!("Could not read keystore {}: {e}", path.display()))?
+                .permissions()
+                .mode();
+            if mode & 0o077 != 0 {
+                return Err(anyhow::anyhow!(
+                    "Keystore {} is readable by other users, run chmod 600 on it",
+                    path.display()
+                ));
+            }
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Could not read keystore {}: {e}", path.display()))?;
+        let bytes = decode_hex(contents.trim()).ok_or(anyhow::anyhow!(
+            "Keystore {} is not a hex key",
+            path.display()
+        ))?;
+        let key = SigningKey::from_bytes(&bytes)
+            .map_err(|_| anyhow::anyhow!("Keystore {} is not a secp256k1 key", path.display()))?;
+        Ok(Self { key })
+    }
+}
+
+impl fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn public_key(&self) -> PublicKey {
+        PublicKey::from(self.key.verifying_key())
+    }
+
+    async fn sign(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let signature: Signature = self.key.try_sign(bytes).map_err(|e| anyhow::anyhow!(e))?;
+        Ok(signature.as_ref().to_vec())
+    }
+}
+
+/// What a [`CommandSigner`] reads on stdin, and a [`RemoteSigner`] posts to `sign`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignRequest {
+    pub public_key: Binary,
+    pub sign_bytes: Binary,
+}
+
+/// What a [`CommandSigner`] writes to stdout, and a [`RemoteSigner`] responds with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignResponse {
+    pub signature: Binary,
+}
+
+/// Runs a command for every signature, such as a hardware wallet or KMS client. The command
+/// reads a JSON [`SignRequest`] on stdin and writes a JSON [`SignResponse`] to stdout.
+#[derive(Debug, Clone)]
+pub struct CommandSigner {
+    program: String,
+    args: Vec<String>,
+    public_key: PublicKey,
+}
+
+impl CommandSigner {
+    /// Sign with `command`, a program followed by its arguments.
+    pub fn new(command: &[String], public_key: PublicKey) -> Result<Self> {
+        let (program, args) = command
+            .split_first()
+            .ok_or(anyhow::anyhow!("The signer command is empty"))?;
+        Ok(Self {
+            program: program.clone(),
+            args: args.to_vec(),
+            public_key,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for CommandSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    async fn sign(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let request = serde_json::to_vec(&SignRequest {
+            public_key: Binary(self.public_key.to_bytes()),
+            sign_bytes: Binary(bytes.to_vec()),
+        })?;
+        let (program, args) = (self.program.clone(), self.args.clone());
+        let output = tokio::task::spawn_blocking(move || -> Result<_> {
+            let mut child = Command::new(&program)
+                .args(&args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .map_err(|e| anyhow::anyhow!("Could not run signer {program}: {e}"))?;
+            child.stdin.take().unwrap().write_all(&request)?;
+            Ok(child.wait_with_output()?)
+        })
+        .await??;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Signer {} failed with {}",
+                self.program,
+                output.status
+            ));
+        }
+        let response: SignResponse = serde_json::from_slice(&output.stdout)
+            .map_err(|e| anyhow::anyhow!("Invalid response from signer {}: {e}", self.program))?;
+        verify(&self.program, self.public_key, bytes, &response.signature)?;
+        Ok(response.signature.to_vec())
+    }
+}
+
+/// Asks a signing service over HTTP, posting a [`SignRequest`] to `{url}/sign`.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    url: String,
+    public_key: PublicKey,
+    transport: Arc<dyn Transport>,
+}
+
+impl RemoteSigner {
+    pub fn new(url: impl Into<String>, public_key: PublicKey) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            public_key,
+            transport: Arc::new(HttpTransport),
+        }
+    }
+
+    /// Send requests through `transport` instead of HTTP, such as a [`SignerService`] in
+    /// tests.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    async fn sign(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let request = SignRequest {
+            public_key: Binary(self.public_key.to_bytes()),
+            sign_bytes: Binary(bytes.to_vec()),
+        };
+        let response = self
+            .transport
+            .post(&self.url, "sign", serde_json::to_value(request)?)
+            .await?;
+        let response: SignResponse = serde_json::from_value(response.clone()).map_err(|_| {
+            anyhow::anyhow!("Invalid response from signer {}: {response}", self.url)
+        })?;
+        verify(&self.url, self.public_key, bytes, &response.signature)?;
+        Ok(response.signature.to_vec())
+    }
+}
+
+/// Serves the [`RemoteSigner`] API from another signer, standing in for a signing service in
+/// tests.
+#[derive(Debug, Clone)]
+pub struct SignerService {
+    signer: Arc<dyn Signer>,
+}
+
+impl SignerService {
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self { signer }
+    }
+}
+
+#[async_trait]
+impl Transport for SignerService {
+    async fn get(&self, _url: &str, path: &str) -> Result<Value> {
+        Err(anyhow::anyhow!("The signer has no GET {path}"))
+    }
+
+    async fn post(&self, _url: &str, path: &str, body: Value) -> Result<Value> {
+        if path != "sign" {
+            return Err(anyhow::anyhow!("The signer has no POST {path}"));
+        }
+        let request: SignRequest = serde_json::from_value(body)?;
+        if request.public_key.as_slice() != self.signer.public_key().to_bytes() {
+            return Err(anyhow::anyhow!(
+                "The signer has no key {}",
+                request.public_key.to_base64()
+            ));
+        }
+        let signature = self.signer.sign(&request.sign_bytes).await?;
+        Ok(json!(SignResponse {
+            signature: Binary(signature),
+        }))
+    }
+}
+
+/// Check a signature from outside the process, so a signer that returns garbage or signs with
+/// another key fails here rather than when the transaction is broadcast.
+fn verify(signer: &str, public_key: PublicKey, bytes: &[u8], signature: &[u8]) -> Result<()> {
+    if signature.len() != 64 {
+        return Err(anyhow::anyhow!(
+            "Signer {signer} returned a {} byte signature, expected 64",
+            signature.len()
+        ));
+    }
+    let key = VerifyingKey::from_sec1_bytes(&public_key.to_bytes())
+        .map_err(|_| anyhow::anyhow!("Signer {signer} has no secp256k1 public key"))?;
+    let signature = Signature::try_from(signature)
+        .map_err(|_| anyhow::anyhow!("Signer {signer} returned an invalid signature"))?;
+    key.verify(bytes, &signature).map_err(|_| {
+        anyhow::anyhow!(
+            "Signer {signer} returned a signature that doesn't match its public key {}",
+            Binary(public_key.to_bytes()).to_base64()
+        )
+    })
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -4,20 +4,111 @@ use anyhow::Result;
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine};
 use cosmrs::{
-    cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode},
-    tx::{mode_info::Single, Body, Fee, ModeInfo, Msg, SignDoc, SignMode, SignerInfo},
-    AccountId, Any, Denom,
+    proto::cosmos::{
+        base::v1beta1::Coin as ProtoCoin,
+        tx::v1beta1::{Fee as ProtoFee, TxRaw},
+    },
+    tx::{mode_info::Single, Body, Fee, ModeInfo, Msg, SignMode as ProtoSignMode, SignerInfo},
+    AccountId, Any,
 };
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use prost::Message;
 use serde::Serialize;
 use serde_json::{json, Value};
 
-use crate::{account::AccountWithInfo, Network, Querier, QueryClient};
+use crate::{account::AccountWithInfo, CodeInfo, ContractInfo, Network, Querier, QueryClient};
 
-use super::utils::cosmwasm_coins_to_cosmrs_coins;
+use super::{
+    messages::{self, AccessConfig, VoteOption},
+    offline::{OfflineTx, SignMode, TxSigner},
+    utils::mul_ceil,
+};
 
 pub const TIMEOUT_BLOCK_AMOUNT: u32 = 100;
 
+/// Overrides of how the fee of a transaction is set, see
+/// [`Executor::execute_any_with_fee`]. By default the gas is simulated and priced at the
+/// network's gas price.
+#[derive(Debug, Clone, Default)]
+pub struct FeeOptions {
+    /// Gas limit to use instead of simulating the transaction.
+    pub gas_limit: Option<u64>,
+    /// Fee to pay instead of pricing the gas.
+    pub amount: Option<Coin>,
+    /// Denom to pay the fee in instead of the network's gas denom.
+    pub denom: Option<String>,
+    /// Price of a unit of gas instead of the network's, in `denom`.
+    pub gas_price: Option<Decimal>,
+    /// Address whose feegrant pays the fee.
+    pub granter: Option<String>,
+    /// Address that pays the fee, which must also sign the transaction.
+    pub payer: Option<String>,
+}
+
+impl FeeOptions {
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn with_amount(mut self, amount: Coin) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Pay in `denom` at `gas_price` per unit of gas.
+    pub fn with_denom(mut self, denom: impl Into<String>, gas_price: Decimal) -> Self {
+        self.denom = Some(denom.into());
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    pub fn with_granter(mut self, granter: impl Into<String>) -> Self {
+        self.granter = Some(granter.into());
+        self
+    }
+
+    pub fn with_payer(mut self, payer: impl Into<String>) -> Self {
+        self.payer = Some(payer.into());
+        self
+    }
+
+    /// The fee of a transaction using `gas_limit` on `network`, checked against its
+    /// `max_fee`.
+    pub async fn to_fee(self, network: &Network, gas_limit: u64) -> Result<ProtoFee> {
+        let amount = match self.amount {
+            Some(amount) => amount,
+            None => {
+                let gas_price = match self.gas_price {
+                    Some(gas_price) => gas_price,
+                    None => network.current_gas_price().await?,
+                };
+                Coin {
+                    denom: self.denom.unwrap_or_else(|| network.gas_denom.clone()),
+                    amount: mul_ceil(Uint128::from(gas_limit), gas_price)?,
+                }
+            }
+        };
+        network.check_fee(&amount)?;
+        let address = |address: Option<String>| match address {
+            Some(address) => match AccountId::from_str(&address) {
+                Ok(_) => Ok(address),
+                Err(_) => Err(anyhow::anyhow!("Invalid fee address {address}")),
+            },
+            None => Ok(String::new()),
+        };
+        Ok(ProtoFee {
+            amount: vec![ProtoCoin {
+                denom: amount.denom,
+                amount: amount.amount.to_string(),
+            }],
+            gas_limit,
+            payer: address(self.payer)?,
+            granter: address(self.granter)?,
+        })
+    }
+}
+
 #[async_trait]
 pub trait Executor {
     /// Send a WASM execute
@@ -29,11 +120,50 @@ pub trait Executor {
         memo: Option<String>,
     ) -> Result<String>
     where
-        Req: Serialize + ?Sized + Sync + Clone;
+        Req: Serialize + Sync + Clone;
 
-    /// Send a storecode
+    /// Send a WASM execute on behalf of `granter`, which granted this account permission to
+    /// execute contracts through authz
+    async fn execute_smart_as<Req>(
+        &self,
+        granter: String,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = messages::execute(&granter, &address, serde_json::to_vec(message)?, funds)?;
+        self.execute_authz(vec![msg], memo).await
+    }
+
+    /// Send a storecode, leaving who may instantiate the code to the chain's default
     async fn store_code(&self, bytecode: Vec<u8>, memo: Option<String>) -> Result<String>;
 
+    /// Send a storecode that only lets `access` instantiate the code
+    async fn store_code_with_access(
+        &self,
+        bytecode: Vec<u8>,
+        access: AccessConfig,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::store_code(&self.address(), bytecode, Some(access))?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Change who may instantiate code stored by this account
+    async fn update_instantiate_config(
+        &self,
+        code_id: u64,
+        access: AccessConfig,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::update_instantiate_config(&self.address(), code_id, access)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
     /// Send a WASM instantiate
     async fn instantiate<Req>(
         &self,
@@ -45,7 +175,37 @@ pub trait Executor {
         memo: Option<String>,
     ) -> Result<String>
     where
-        Req: Serialize + ?Sized + Sync + Clone;
+        Req: Serialize + Sync + Clone;
+
+    /// Send a WASM instantiate at an address known in advance from the code checksum, the
+    /// sender and `salt`, see [`predict_address`](crate::utils::predict_address)
+    #[allow(clippy::too_many_arguments)]
+    async fn instantiate2<Req>(
+        &self,
+        code_id: u64,
+        msg: &Req,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        salt: Vec<u8>,
+        fix_msg: bool,
+        memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = messages::instantiate2(
+            &self.address(),
+            code_id,
+            serde_json::to_vec(msg)?,
+            funds,
+            label,
+            admin,
+            salt,
+            fix_msg,
+        )?;
+        self.execute_any(vec![msg], memo).await
+    }
 
     /// Send a WASM migrate
     async fn migrate<Req>(
@@ -56,12 +216,260 @@ pub trait Executor {
         memo: Option<String>,
     ) -> Result<String>
     where
-        Req: Serialize + ?Sized + Sync + Clone;
+        Req: Serialize + Sync + Clone;
+
+    /// Send a WASM admin update
+    async fn update_admin(
+        &self,
+        address: String,
+        new_admin: String,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::update_admin(&self.address(), &address, &new_admin)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Send a WASM admin removal, after which the contract can't be migrated
+    async fn clear_admin(&self, address: String, memo: Option<String>) -> Result<String> {
+        let msg = messages::clear_admin(&self.address(), &address)?;
+        self.execute_any(vec![msg], memo).await
+    }
 
     /// Execute generic messages
     async fn execute<Req>(&self, messages: Vec<Req>, memo: Option<String>) -> Result<String>
     where
-        Req: Msg + Sync + Send + Clone;
+        Req: Msg + Sync + Send + Clone,
+    {
+        let messages = messages
+            .iter()
+            .map(|m| m.to_any().map_err(|e| anyhow::anyhow!(e)))
+            .collect::<Result<Vec<Any>>>()?;
+        self.execute_any(messages, memo).await
+    }
+
+    /// Send coins from this account to `to_address`
+    async fn bank_send(
+        &self,
+        to_address: String,
+        amount: Vec<Coin>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::send(&self.address(), &to_address, amount)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Send coins from this account to several addresses in one message
+    async fn multi_send(
+        &self,
+        outputs: Vec<(String, Vec<Coin>)>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::multi_send(&self.address(), outputs)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Delegate to a validator
+    async fn delegate(
+        &self,
+        validator_address: String,
+        amount: Coin,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::delegate(&self.address(), &validator_address, amount)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Undelegate from a validator
+    async fn undelegate(
+        &self,
+        validator_address: String,
+        amount: Coin,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::undelegate(&self.address(), &validator_address, amount)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Move a delegation from one validator to another
+    async fn redelegate(
+        &self,
+        src_validator_address: String,
+        dst_validator_address: String,
+        amount: Coin,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::redelegate(
+            &self.address(),
+            &src_validator_address,
+            &dst_validator_address,
+            amount,
+        )?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Withdraw staking rewards from each of the validators
+    async fn withdraw_rewards(
+        &self,
+        validator_addresses: Vec<String>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let delegator = self.address();
+        let msgs = validator_addresses
+            .iter()
+            .map(|validator| messages::withdraw_rewards(&delegator, validator))
+            .collect::<Result<Vec<_>>>()?;
+        self.execute_any(msgs, memo).await
+    }
+
+    /// Vote on a governance proposal
+    async fn vote(
+        &self,
+        proposal_id: u64,
+        option: VoteOption,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::vote(&self.address(), proposal_id, option)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Create the Kujira tokenfactory denom `factory/{address}/{nonce}`
+    async fn create_denom(&self, nonce: String, memo: Option<String>) -> Result<String> {
+        let msg = messages::create_denom(&self.address(), &nonce)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Mint a tokenfactory denom administered by this account
+    async fn mint(&self, amount: Coin, recipient: String, memo: Option<String>) -> Result<String> {
+        let msg = messages::mint(&self.address(), amount, &recipient)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Burn a tokenfactory denom administered by this account from its balance
+    async fn burn(&self, amount: Coin, memo: Option<String>) -> Result<String> {
+        let msg = messages::burn(&self.address(), amount)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Execute messages on behalf of the accounts that granted this one permission through
+    /// authz, the signer of each message being its granter
+    async fn execute_authz(&self, messages: Vec<Any>, memo: Option<String>) -> Result<String> {
+        let msg = messages::exec(&self.address(), messages)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Let `grantee` send messages of type `msg_type_url` for this account through authz,
+    /// until `expiration` in seconds since the Unix epoch if given
+    async fn grant(
+        &self,
+        grantee: String,
+        msg_type_url: String,
+        expiration: Option<u64>,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::grant(&self.address(), &grantee, &msg_type_url, expiration)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// Withdraw a grant made with [`Executor::grant`]
+    async fn revoke(
+        &self,
+        grantee: String,
+        msg_type_url: String,
+        memo: Option<String>,
+    ) -> Result<String> {
+        let msg = messages::revoke(&self.address(), &grantee, &msg_type_url)?;
+        self.execute_any(vec![msg], memo).await
+    }
+
+    /// [`Executor::execute_smart`] with a fee set by `fee`
+    async fn execute_smart_with_fee<Req>(
+        &self,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = messages::execute(
+            &self.address(),
+            &address,
+            serde_json::to_vec(message)?,
+            funds,
+        )?;
+        self.execute_any_with_fee(vec![msg], memo, fee).await
+    }
+
+    /// Send a storecode with a fee set by `fee`, that only lets `access` instantiate the code
+    /// if given
+    async fn store_code_with_fee(
+        &self,
+        bytecode: Vec<u8>,
+        access: Option<AccessConfig>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        let msg = messages::store_code(&self.address(), bytecode, access)?;
+        self.execute_any_with_fee(vec![msg], memo, fee).await
+    }
+
+    /// [`Executor::instantiate`] with a fee set by `fee`
+    #[allow(clippy::too_many_arguments)]
+    async fn instantiate_with_fee<Req>(
+        &self,
+        code_id: u64,
+        msg: &Req,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = messages::instantiate(
+            &self.address(),
+            code_id,
+            serde_json::to_vec(msg)?,
+            funds,
+            label,
+            admin,
+        )?;
+        self.execute_any_with_fee(vec![msg], memo, fee).await
+    }
+
+    /// [`Executor::migrate`] with a fee set by `fee`
+    async fn migrate_with_fee<Req>(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: &Req,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = messages::migrate(&self.address(), &address, code_id, serde_json::to_vec(msg)?)?;
+        self.execute_any_with_fee(vec![msg], memo, fee).await
+    }
+
+    /// Execute messages already encoded as protobuf `Any`s
+    async fn execute_any(&self, messages: Vec<Any>, memo: Option<String>) -> Result<String>;
+
+    /// Execute messages already encoded as protobuf `Any`s, with a fee set by `fee`
+    async fn execute_any_with_fee(
+        &self,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>;
+
+    /// The address transactions are sent from
+    fn address(&self) -> String;
 }
 
 pub struct SigningClient {
@@ -74,37 +482,35 @@ impl SigningClient {
         Self { network, account }
     }
 
+    /// Sign with `sign_mode` instead of the account's sign mode.
+    pub fn with_sign_mode(mut self, sign_mode: SignMode) -> Self {
+        self.account.sign_mode = sign_mode;
+        self
+    }
+
     pub fn into_query(self) -> QueryClient {
         QueryClient::new(self.network)
     }
 
-    pub async fn estimate_gas<Req>(
+    pub async fn estimate_gas(
         &self,
-        messages: Vec<Req>,
+        messages: Vec<Any>,
         memo: Option<String>,
         block_height: u32,
-        acc_num: u64,
         seq_num: u64,
-    ) -> Result<u64>
-    where
-        Req: Msg,
-    {
+    ) -> Result<u64> {
         let body = Body::new(
-            messages
-                .into_iter()
-                .map(|m| m.to_any().unwrap())
-                .collect::<Vec<Any>>(),
+            messages,
             memo.unwrap_or_default(),
             block_height + TIMEOUT_BLOCK_AMOUNT,
         );
 
+        // Simulation doesn't check signatures, so the signer isn't asked for one.
         let tx_raw = {
-            let (pk, sk) = self.account.get_keypair()?;
-
             let auth_info = SignerInfo {
-                public_key: Some(pk.into()),
+                public_key: Some(self.account.public_key().into()),
                 mode_info: ModeInfo::Single(Single {
-                    mode: SignMode::Unspecified,
+                    mode: ProtoSignMode::Unspecified,
                 }),
                 sequence: seq_num,
             }
@@ -115,26 +521,13 @@ impl SigningClient {
                 granter: None,
             });
 
-            let sign_doc = SignDoc::new(
-                &body,
-                &auth_info,
-                &self
-                    .network
-                    .chain_id
-                    .clone()
-                    .try_into()
-                    .map_err(|_| anyhow::anyhow!("Invalid chain id"))?,
-                acc_num,
-            )
-            .map_err(|e| anyhow::anyhow!(e))?;
+            let tx_raw = TxRaw {
+                body_bytes: body.into_bytes().map_err(|e| anyhow::anyhow!(e))?,
+                auth_info_bytes: auth_info.into_bytes().map_err(|e| anyhow::anyhow!(e))?,
+                signatures: vec![vec![]],
+            };
 
-            let tx_raw = sign_doc
-                .sign(&sk)
-                .map_err(|e| anyhow::anyhow!(e))?
-                .to_bytes()
-                .map_err(|e| anyhow::anyhow!(e))?;
-
-            STANDARD.encode(tx_raw)
+            STANDARD.encode(tx_raw.encode_to_vec())
         };
 
         let res = self
@@ -150,8 +543,9 @@ impl SigningClient {
         let gas = res["gas_info"]["gas_used"]
             .as_str()
             .ok_or(anyhow::anyhow!("Error simulating transaction: {res:#?}"))?
-            .parse::<f64>()?;
-        let gas = (gas * self.network.gas_adjustment).ceil() as u64;
+            .parse::<u64>()?;
+        let gas = mul_ceil(Uint128::from(gas), self.network.gas_adjustment)?;
+        let gas = u64::try_from(gas.u128())?;
 
         Ok(gas)
     }
@@ -159,6 +553,19 @@ impl SigningClient {
     pub fn get_account(&self) -> &AccountWithInfo {
         &self.account
     }
+
+    /// Warn before uploading code to a mainnet that anyone could instantiate.
+    fn check_access(&self, access: Option<&AccessConfig>) {
+        if !self.network.mainnet {
+            return;
+        }
+        if let Some(warning) = messages::open_access_warning(access) {
+            eprintln!(
+                "Warning: uploading to mainnet {}, {warning}",
+                self.network.chain_id
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -176,6 +583,16 @@ impl Querier for SigningClient {
         let query_client = QueryClient::new(self.network.clone());
         query_client.wait_for_transaction(tx_hash).await
     }
+
+    async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        let query_client = QueryClient::new(self.network.clone());
+        query_client.contract_info(address).await
+    }
+
+    async fn code_info(&self, code_id: u64) -> Result<CodeInfo> {
+        let query_client = QueryClient::new(self.network.clone());
+        query_client.code_info(code_id).await
+    }
 }
 
 #[async_trait]
@@ -188,27 +605,64 @@ impl Executor for SigningClient {
         memo: Option<String>,
     ) -> Result<String>
     where
-        Req: Serialize + ?Sized + Sync + Clone,
+        Req: Serialize + Sync + Clone,
     {
-        let msg = MsgExecuteContract {
-            sender: self.account.address.clone(),
-            contract: AccountId::from_str(&address)
-                .map_err(|_| anyhow::anyhow!("Invalid contract address"))?,
-            msg: serde_json::to_vec(message)?,
-            funds: cosmwasm_coins_to_cosmrs_coins(funds),
-        };
+        self.execute_smart_with_fee(address, message, funds, memo, FeeOptions::default())
+            .await
+    }
 
-        self.execute(vec![msg], memo).await
+    async fn execute_smart_with_fee<Req>(
+        &self,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = match &self.account.acting_as {
+            Some(granter) => {
+                let msg =
+                    messages::execute(granter, &address, serde_json::to_vec(message)?, funds)?;
+                messages::exec(&self.address(), vec![msg])?
+            }
+            None => messages::execute(
+                &self.address(),
+                &address,
+                serde_json::to_vec(message)?,
+                funds,
+            )?,
+        };
+        self.execute_any_with_fee(vec![msg], memo, fee).await
     }
 
     async fn store_code(&self, bytecode: Vec<u8>, memo: Option<String>) -> Result<String> {
-        let msg = MsgStoreCode {
-            sender: self.account.address.clone(),
-            wasm_byte_code: bytecode,
-            instantiate_permission: None,
-        };
+        self.store_code_with_fee(bytecode, None, memo, FeeOptions::default())
+            .await
+    }
 
-        self.execute(vec![msg], memo).await
+    async fn store_code_with_access(
+        &self,
+        bytecode: Vec<u8>,
+        access: AccessConfig,
+        memo: Option<String>,
+    ) -> Result<String> {
+        self.store_code_with_fee(bytecode, Some(access), memo, FeeOptions::default())
+            .await
+    }
+
+    async fn store_code_with_fee(
+        &self,
+        bytecode: Vec<u8>,
+        access: Option<AccessConfig>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        self.check_access(access.as_ref());
+        let msg = messages::store_code(&self.address(), bytecode, access)?;
+        self.execute_any_with_fee(vec![msg], memo, fee).await
     }
 
     async fn instantiate<Req>(
@@ -221,22 +675,18 @@ impl Executor for SigningClient {
         memo: Option<String>,
     ) -> Result<String>
     where
-        Req: Serialize + ?Sized + Sync + Clone,
+        Req: Serialize + Sync + Clone,
     {
-        let msg = MsgInstantiateContract {
-            sender: self.account.address.clone(),
-            msg: serde_json::to_vec(msg)?,
+        self.instantiate_with_fee(
             code_id,
-            admin: admin
-                .map(|a| {
-                    AccountId::from_str(&a).map_err(|_| anyhow::anyhow!("Invalid admin address"))
-                })
-                .transpose()?,
+            msg,
+            funds,
             label,
-            funds: cosmwasm_coins_to_cosmrs_coins(funds),
-        };
-
-        self.execute(vec![msg], memo).await
+            admin,
+            memo,
+            FeeOptions::default(),
+        )
+        .await
     }
 
     async fn migrate<Req>(
@@ -247,97 +697,61 @@ impl Executor for SigningClient {
         memo: Option<String>,
     ) -> Result<String>
     where
-        Req: Serialize + ?Sized + Sync + Clone,
+        Req: Serialize + Sync + Clone,
     {
-        let msg = MsgMigrateContract {
-            sender: self.account.address.clone(),
-            contract: AccountId::from_str(&address)
-                .map_err(|_| anyhow::anyhow!("Invalid contract address"))?,
-            code_id,
-            msg: serde_json::to_vec(msg)?,
-        };
+        self.migrate_with_fee(address, code_id, msg, memo, FeeOptions::default())
+            .await
+    }
 
-        self.execute(vec![msg], memo).await
+    async fn execute_any(&self, messages: Vec<Any>, memo: Option<String>) -> Result<String> {
+        self.execute_any_with_fee(messages, memo, FeeOptions::default())
+            .await
     }
 
-    async fn execute<Req>(&self, messages: Vec<Req>, memo: Option<String>) -> Result<String>
-    where
-        Req: Msg + Sync + Send + Clone,
-    {
+    async fn execute_any_with_fee(
+        &self,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        fee: FeeOptions,
+    ) -> Result<String> {
+        // Another payer must sign too, which takes the offline flow of `aq tx`.
+        if let Some(payer) = fee.payer.as_ref().filter(|payer| **payer != self.address()) {
+            return Err(anyhow::anyhow!(
+                "The fee payer {payer} must also sign, which a SigningClient can't do. Have it grant a feegrant and set the granter instead"
+            ));
+        }
         let block_height = self.network.current_block_height().await?;
-        let body = Body::new(
-            messages
-                .clone()
-                .into_iter()
-                .map(|m| m.to_any().unwrap())
-                .collect::<Vec<Any>>(),
-            memo.clone().unwrap_or_default(),
-            block_height + TIMEOUT_BLOCK_AMOUNT,
-        );
-
         let (acc, sequence) = self
             .network
             .account_sequence_numbers(self.account.address.to_string())
             .await?;
 
-        let estimated_gas = self
-            .estimate_gas(messages, memo, block_height, acc, sequence)
-            .await?;
-
-        let gas_fee = u128::from((estimated_gas as f64 * self.network.gas_price).ceil() as u64);
-        let gas_fee = Fee::from_amount_and_gas(
-            cosmrs::Coin {
-                denom: Denom::from_str(self.network.gas_denom.as_str()).unwrap(),
-                amount: gas_fee,
-            },
-            estimated_gas,
-        );
-
-        let tx_raw = {
-            let (pk, sk) = self.account.get_keypair()?;
-            let auth_info = SignerInfo::single_direct(Some(pk), sequence).auth_info(gas_fee);
-
-            let sign_doc = SignDoc::new(
-                &body,
-                &auth_info,
-                &self
-                    .network
-                    .chain_id
-                    .clone()
-                    .try_into()
-                    .map_err(|_| anyhow::anyhow!("Invalid chain id"))?,
-                acc,
-            )
-            .map_err(|e| anyhow::anyhow!(e))?;
-
-            let tx_raw = sign_doc
-                .sign(&sk)
-                .map_err(|e| anyhow::anyhow!(e))?
-                .to_bytes()
-                .map_err(|e| anyhow::anyhow!(e))?;
-            STANDARD.encode(tx_raw)
+        let gas_limit = match fee.gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => {
+                self.estimate_gas(messages.clone(), memo.clone(), block_height, sequence)
+                    .await?
+            }
         };
+        let fee = fee.to_fee(&self.network, gas_limit).await?;
+
+        let mut tx = OfflineTx::new(
+            &self.network.chain_id,
+            &self.address(),
+            acc,
+            sequence,
+            &TxSigner::Single(self.account.public_key()),
+            messages,
+            memo,
+            (block_height + TIMEOUT_BLOCK_AMOUNT).into(),
+            fee,
+            self.account.sign_mode,
+        )?;
+        tx.sign(self.account.signer()).await?;
+        tx.broadcast(&self.network).await
+    }
 
-        let post_data = json!({
-            "tx_bytes": tx_raw,
-            "mode": "BROADCAST_MODE_SYNC",
-        });
-
-        let res = self
-            .network
-            .post("cosmos/tx/v1beta1/txs", &post_data)
-            .await?;
-
-        if res["tx_response"]["code"]
-            .as_u64()
-            .map_or(false, |c| c == 11)
-        {
-            return Err(anyhow::anyhow!("Insufficient gas"));
-        }
-
-        Ok(res["tx_response"]["txhash"]
-            .as_str()
-            .ok_or(anyhow::anyhow!("Error parsing txhash. Response: {res:#?}"))?
-            .to_string())
+    fn address(&self) -> String {
+        self.account.address.to_string()
     }
 }
@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Environment variable holding a fixture file to record LCD traffic into.
+pub const RECORD_ENV_VAR: &str = "AQUARIUM_RECORD";
+/// Environment variable holding a fixture file to replay LCD traffic from, offline.
+pub const REPLAY_ENV_VAR: &str = "AQUARIUM_REPLAY";
+
+/// Sends the LCD requests made through [`Network::get`](crate::Network::get) and
+/// [`Network::post`](crate::Network::post). Paths are relative to the LCD address.
+#[async_trait]
+pub trait Transport: Send + Sync + fmt::Debug {
+    async fn get(&self, lcd_addr: &str, path: &str) -> Result<Value>;
+
+    async fn post(&self, lcd_addr: &str, path: &str, body: Value) -> Result<Value>;
+}
+
+/// Sends requests over HTTP, the default.
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransport;
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn get(&self, lcd_addr: &str, path: &str) -> Result<Value> {
+        let client = Client::new();
+        let path = format!("{lcd_addr}/{path}");
+        Ok(client.get(path).send().await?.json().await?)
+    }
+
+    async fn post(&self, lcd_addr: &str, path: &str, body: Value) -> Result<Value> {
+        let client = Client::new();
+        let path = format!("{lcd_addr}/{path}");
+        Ok(client.post(path).json(&body).send().await?.json().await?)
+    }
+}
+
+/// One request and its response, as stored in a fixture file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    pub response: Value,
+}
+
+/// Sends requests over HTTP and records every exchange, for [`ReplayTransport`] to serve
+/// back. The fixture file is written by [`RecordingTransport::finish`], or when the transport
+/// is dropped, so it is complete even if the task fails.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    recording: Mutex<Recording>,
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    exchanges: Vec<Exchange>,
+    /// Whether the file has every exchange so far.
+    saved: bool,
+}
+
+impl RecordingTransport {
+    /// Record into `path`, replacing any fixture already there.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(HttpTransport),
+            path: path.into(),
+            recording: Mutex::new(Recording::default()),
+        }
+    }
+
+    /// Record the requests sent through `transport` instead of HTTP.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.inner = transport;
+        self
+    }
+
+    /// Write the exchanges recorded so far to the fixture file. Recording can continue
+    /// afterwards, and the file is written again when the transport is dropped.
+    pub fn finish(&self) -> Result<()> {
+        let mut recording = self.recording.lock().unwrap();
+        if recording.saved {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Replace the fixture in one rename, so an interrupted write never leaves half of one.
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        std::fs::write(&temp, serde_json::to_string_pretty(&recording.exchanges)?)?;
+        std::fs::rename(&temp, &self.path)?;
+        recording.saved = true;
+        Ok(())
+    }
+
+    fn record(&self, exchange: Exchange) {
+        let mut recording = self.recording.lock().unwrap();
+        recording.exchanges.push(exchange);
+        recording.saved = false;
+    }
+}
+
+impl Drop for RecordingTransport {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            eprintln!("Could not write fixture {}: {e}", self.path.display());
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn get(&self, lcd_addr: &str, path: &str) -> Result<Value> {
+        let response = self.inner.get(lcd_addr, path).await?;
+        self.record(Exchange {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            body: None,
+            response: response.clone(),
+        });
+        Ok(response)
+    }
+
+    async fn post(&self, lcd_addr: &str, path: &str, body: Value) -> Result<Value> {
+        let response = self.inner.post(lcd_addr, path, body.clone()).await?;
+        self.record(Exchange {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            body: Some(body),
+            response: response.clone(),
+        });
+        Ok(response)
+    }
+}
+
+/// Method, path and body of a request, identifying its recorded responses.
+type RequestKey = (String, String, String);
+
+/// Serves the responses of a fixture written by [`RecordingTransport`], without network
+/// access.
+///
+/// Requests are matched by method, path and body. Identical requests get their recorded
+/// responses in order, and the last one once those run out, so polling replays as recorded.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    path: PathBuf,
+    responses: Mutex<HashMap<RequestKey, (Vec<Value>, usize)>>,
+}
+
+impl ReplayTransport {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Could not read fixture {}: {e}", path.display()))?;
+        let exchanges: Vec<Exchange> = serde_json::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Could not parse fixture {}: {e}", path.display()))?;
+        let mut responses = HashMap::<_, (Vec<Value>, usize)>::new();
+        for exchange in exchanges {
+            let key = Self::key(&exchange.method, &exchange.path, exchange.body.as_ref());
+            responses.entry(key).or_default().0.push(exchange.response);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            responses: Mutex::new(responses),
+        })
+    }
+
+    fn key(method: &str, path: &str, body: Option<&Value>) -> RequestKey {
+        (
+            method.to_string(),
+            path.to_string(),
+            body.map(|body| body.to_string()).unwrap_or_default(),
+        )
+    }
+
+    fn respond(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+        let mut responses = self.responses.lock().unwrap();
+        let (recorded, next) =
+            responses
+                .get_mut(&Self::key(method, path, body))
+                .ok_or(anyhow::anyhow!(
+            "No recorded response for {method} {path} in {}, record it again with {RECORD_ENV_VAR}",
+            self.path.display()
+        ))?;
+        let response = recorded[(*next).min(recorded.len() - 1)].clone();
+        *next += 1;
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn get(&self, _lcd_addr: &str, path: &str) -> Result<Value> {
+        self.respond("GET", path, None)
+    }
+
+    async fn post(&self, _lcd_addr: &str, path: &str, body: Value) -> Result<Value> {
+        self.respond("POST", path, Some(&body))
+    }
+}
+
+/// The transport selected by [`RECORD_ENV_VAR`] or [`REPLAY_ENV_VAR`], if either is set.
+pub fn transport_from_env() -> Result<Option<Arc<dyn Transport>>> {
+    if let Ok(path) = std::env::var(REPLAY_ENV_VAR) {
+        return Ok(Some(Arc::new(ReplayTransport::load(path)?)));
+    }
+    if let Ok(path) = std::env::var(RECORD_ENV_VAR) {
+        return Ok(Some(Arc::new(RecordingTransport::new(path))));
+    }
+    Ok(None)
+}
@@ -1,5 +1,23 @@
+use std::str::FromStr;
+
 use anyhow::Result;
+use cosmrs::AccountId;
+use cosmwasm_std::{Decimal, HexBinary, Uint128, Uint256};
+use serde::Serialize;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Fail unless a transaction receipt from `wait_for_transaction` reports success.
+pub fn ensure_tx_success(receipt: &Value) -> Result<()> {
+    match receipt["tx_response"]["code"].as_u64() {
+        Some(0) => Ok(()),
+        Some(code) => Err(anyhow::anyhow!(
+            "Transaction failed with code {code}: {}",
+            receipt["tx_response"]["raw_log"]
+        )),
+        None => Err(anyhow::anyhow!("Error parsing transaction: {receipt:#}")),
+    }
+}
 
 pub fn parse_code_id(response: &Value) -> Result<u64> {
     let code_id_event = response["tx_response"]["logs"].as_array().and_then(|logs| {
@@ -61,4 +79,96 @@ pub(crate) fn cosmwasm_coins_to_cosmrs_coins(coin: Vec<cosmwasm_std::Coin>) -> V
 
 pub(crate) fn cosmwasm_coin_to_cosmrs_coin(coin: cosmwasm_std::Coin) -> cosmrs::Coin {
     cosmrs::Coin::new(coin.amount.u128(), &coin.denom).unwrap()
-}
\ No newline at end of file
+}
+/// Parse coins in the `1000ukuji,20uatom` format used by the Cosmos SDK CLI.
+pub fn parse_coins(coins: &str) -> Result<Vec<cosmwasm_std::Coin>> {
+    coins
+        .split(',')
+        .map(str::trim)
+        .filter(|coin| !coin.is_empty())
+        .map(|coin| {
+            let split = coin
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(|| anyhow::anyhow!("Missing denom in coin \"{coin}\""))?;
+            let (amount, denom) = coin.split_at(split);
+            if amount.is_empty() {
+                return Err(anyhow::anyhow!("Missing amount in coin \"{coin}\""));
+            }
+            Ok(cosmwasm_std::coin(amount.parse()?, denom))
+        })
+        .collect()
+}
+
+/// Parse a decimal coin such as a gas price, `0.00125ukuji`, into its amount and denom. The
+/// denom is `None` for a bare amount.
+pub fn parse_dec_coin(coin: &str) -> Result<(Decimal, Option<String>)> {
+    let coin = coin.trim();
+    let (amount, denom) = match coin.find(|c: char| !c.is_ascii_digit() && c != '.') {
+        Some(split) => {
+            let (amount, denom) = coin.split_at(split);
+            (amount, Some(denom.to_string()))
+        }
+        None => (coin, None),
+    };
+    let amount = Decimal::from_str(amount)
+        .map_err(|_| anyhow::anyhow!("Invalid amount in decimal coin \"{coin}\""))?;
+    Ok((amount, denom))
+}
+
+/// `amount * ratio`, rounded up, such as a gas limit times a gas price.
+pub fn mul_ceil(amount: Uint128, ratio: Decimal) -> Result<Uint128> {
+    let one = Uint256::from(Decimal::one().atomics());
+    let product = amount.full_mul(ratio.atomics());
+    let ceil = (product + one - Uint256::one()) / one;
+    Uint128::try_from(ceil).map_err(|e| anyhow::anyhow!(e))
+}
+
+/// The address `instantiate2` gives a contract, computed offline from the hex encoded
+/// checksum of its code, the address of its creator and the salt. The address has the
+/// creator's prefix.
+pub fn predict_address(code_checksum: &str, creator: &str, salt: &[u8]) -> Result<String> {
+    instantiate2_address(code_checksum, creator, salt, &[])
+}
+
+/// [`predict_address`] for an `instantiate2` with `fix_msg`, whose address also depends on
+/// the instantiate message.
+pub fn predict_address_with_msg<T: Serialize + ?Sized>(
+    code_checksum: &str,
+    creator: &str,
+    salt: &[u8],
+    msg: &T,
+) -> Result<String> {
+    instantiate2_address(code_checksum, creator, salt, &serde_json::to_vec(msg)?)
+}
+
+/// The module account address derivation of wasmd's `BuildContractAddressPredictable`.
+fn instantiate2_address(
+    code_checksum: &str,
+    creator: &str,
+    salt: &[u8],
+    msg: &[u8],
+) -> Result<String> {
+    let checksum = HexBinary::from_hex(code_checksum)?;
+    if checksum.len() != 32 {
+        return Err(anyhow::anyhow!("Invalid code checksum {code_checksum}"));
+    }
+    if salt.is_empty() || salt.len() > 64 {
+        return Err(anyhow::anyhow!("The salt must be 1 to 64 bytes long"));
+    }
+    let creator = AccountId::from_str(creator)
+        .map_err(|_| anyhow::anyhow!("Invalid creator address {creator}"))?;
+    let creator_bytes = creator.to_bytes();
+
+    let mut key = b"wasm\0".to_vec();
+    for part in [checksum.as_slice(), &creator_bytes, salt, msg] {
+        key.extend_from_slice(&(part.len() as u64).to_be_bytes());
+        key.extend_from_slice(part);
+    }
+    let address = Sha256::new()
+        .chain_update(Sha256::digest(b"module"))
+        .chain_update(&key)
+        .finalize();
+    AccountId::new(creator.prefix(), &address)
+        .map(|address| address.to_string())
+        .map_err(|e| anyhow::anyhow!(e))
+}
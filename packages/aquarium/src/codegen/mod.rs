@@ -0,0 +1,111 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Result;
+
+use crate::{project::Project, ContractRefs};
+
+pub mod rust;
+pub mod schema;
+pub mod typescript;
+
+pub use schema::ContractSchema;
+
+/// Load the contract schemas in each directory.
+pub fn load_schemas(dirs: &[PathBuf]) -> Result<Vec<ContractSchema>> {
+    let mut schemas = vec![];
+    for dir in dirs {
+        schemas.extend(ContractSchema::load_dir(dir)?);
+    }
+    Ok(schemas)
+}
+
+/// Write a Rust module per contract into `out`, along with a `mod.rs` declaring them.
+/// Returns the paths written.
+pub fn write_rust(schemas: &[ContractSchema], out: &Path) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(out)?;
+    let mut written = vec![];
+    let mut modules = String::from("//! Generated by `aq codegen rust`. Do not edit.\n\n");
+    for schema in schemas {
+        let module = rust::module_name(schema);
+        let path = out.join(format!("{module}.rs"));
+        std::fs::write(&path, rust::generate(schema))?;
+        modules.push_str(&format!(
+            "pub mod {module};\npub use {module}::{};\n",
+            rust::client_name(schema)
+        ));
+        written.push(path);
+    }
+    let path = out.join("mod.rs");
+    std::fs::write(&path, modules)?;
+    written.push(path);
+
+    // Formatting is best effort, the generated code compiles either way.
+    let _ = Command::new("rustfmt")
+        .arg("--edition")
+        .arg("2021")
+        .args(&written)
+        .status();
+    Ok(written)
+}
+
+/// Write `<network>/contracts.json` and `<network>/contracts.ts` into `out` for each network
+/// in `contracts.json`, or only those in `networks` if any are given. Returns the paths written.
+pub fn write_ts_refs(project: &Project, networks: &[String], out: &Path) -> Result<Vec<PathBuf>> {
+    let refs = ContractRefs::load_or_default(project.root.join("contracts.json"))?;
+    let mut names = refs.networks.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    if !networks.is_empty() {
+        if let Some(missing) = networks.iter().find(|n| !refs.networks.contains_key(*n)) {
+            return Err(anyhow::anyhow!(
+                "No contracts deployed to network {missing} in contracts.json"
+            ));
+        }
+        names.retain(|name| networks.contains(name));
+    }
+
+    let mut written = vec![];
+    for name in names {
+        let chain_id = project
+            .config
+            .networks
+            .get(&name)
+            .map(|network| network.chain_id.as_str());
+        let json = typescript::refs_json(&name, chain_id, &refs.networks[&name]);
+        let dir = out.join(&name);
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join("contracts.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&json)? + "\n")?;
+        written.push(path);
+        let path = dir.join("contracts.ts");
+        std::fs::write(&path, typescript::refs_module(&json))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Write a TypeScript module of message types per contract into `out/types`, along with an
+/// `index.ts` exporting each under its contract name. Returns the paths written.
+pub fn write_ts_types(schemas: &[ContractSchema], out: &Path) -> Result<Vec<PathBuf>> {
+    let dir = out.join("types");
+    std::fs::create_dir_all(&dir)?;
+    let mut written = vec![];
+    let mut index = String::from("// Generated by `aq export ts`. Do not edit.\n\n");
+    for schema in schemas {
+        let path = dir.join(format!("{}.ts", schema.name));
+        std::fs::write(&path, typescript::generate(schema))?;
+        index.push_str(&format!(
+            "export * as {} from \"./{}\";\n",
+            schema::pascal_case(&schema.name),
+            schema.name
+        ));
+        written.push(path);
+    }
+    let path = dir.join("index.ts");
+    std::fs::write(&path, index)?;
+    written.push(path);
+    Ok(written)
+}
@@ -0,0 +1,300 @@
+use std::fmt::Write;
+
+use super::schema::{pascal_case, ContractSchema, Field, Ty, TypeDef, TypeDefKind, VariantKind};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+const DERIVES: &str = "#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]\n#[serde(crate = \"::aquarium::internal::serde\")]";
+
+/// The name of the module generated for a contract.
+pub fn module_name(schema: &ContractSchema) -> String {
+    schema.name.replace('-', "_")
+}
+
+/// The name of the client struct generated for a contract.
+pub fn client_name(schema: &ContractSchema) -> String {
+    format!("{}Client", pascal_case(&schema.name))
+}
+
+fn ident(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{name}")
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: &Option<String>) {
+    if let Some(doc) = doc {
+        for line in doc.lines() {
+            let _ = writeln!(
+                out,
+                "{indent}///{}{line}",
+                if line.is_empty() { "" } else { " " }
+            );
+        }
+    }
+}
+
+pub fn rust_type(ty: &Ty) -> String {
+    match ty {
+        Ty::String => "String".to_string(),
+        Ty::Bool => "bool".to_string(),
+        Ty::Int(format) => match format.as_str() {
+            "uint8" => "u8",
+            "uint16" => "u16",
+            "uint32" => "u32",
+            "uint64" => "u64",
+            "uint128" => "u128",
+            "int8" => "i8",
+            "int16" => "i16",
+            "int32" => "i32",
+            "int128" => "i128",
+            _ => "i64",
+        }
+        .to_string(),
+        Ty::Float => "f64".to_string(),
+        Ty::Named(name) => pascal_case(name),
+        Ty::Std(name) => format!("cosmwasm_std::{name}"),
+        Ty::Option(inner) => format!("Option<{}>", rust_type(inner)),
+        Ty::Vec(inner) => format!("Vec<{}>", rust_type(inner)),
+        Ty::Tuple(items) => format!(
+            "({})",
+            items.iter().map(rust_type).collect::<Vec<_>>().join(", ")
+        ),
+        Ty::Map(inner) => format!("std::collections::BTreeMap<String, {}>", rust_type(inner)),
+        Ty::Unit => "()".to_string(),
+        Ty::Json => "serde_json::Value".to_string(),
+    }
+}
+
+/// The type of a field, wrapped in an `Option` if it may be omitted.
+fn field_type(field: &Field) -> String {
+    match (&field.ty, field.optional) {
+        (Ty::Option(_), _) | (_, false) => rust_type(&field.ty),
+        (ty, true) => format!("Option<{}>", rust_type(ty)),
+    }
+}
+
+fn write_fields(out: &mut String, indent: &str, fields: &[Field], public: bool) {
+    let vis = if public { "pub " } else { "" };
+    for field in fields {
+        write_doc(out, indent, &field.doc);
+        let name = ident(&field.name);
+        if name.trim_start_matches("r#") != field.name {
+            let _ = writeln!(out, "{indent}#[serde(rename = \"{}\")]", field.name);
+        }
+        if field.optional {
+            let _ = writeln!(
+                out,
+                "{indent}#[serde(default, skip_serializing_if = \"Option::is_none\")]"
+            );
+        }
+        let _ = writeln!(out, "{indent}{vis}{name}: {},", field_type(field));
+    }
+}
+
+fn write_type(out: &mut String, def: &TypeDef) {
+    write_doc(out, "", &def.doc);
+    let name = pascal_case(&def.name);
+    match &def.kind {
+        TypeDefKind::Struct(fields) => {
+            let _ = writeln!(out, "{DERIVES}\npub struct {name} {{");
+            write_fields(out, "    ", fields, true);
+            let _ = writeln!(out, "}}");
+        }
+        TypeDefKind::Enum(variants) => {
+            let _ = writeln!(out, "{DERIVES}\npub enum {name} {{");
+            for variant in variants {
+                write_doc(out, "    ", &variant.doc);
+                let _ = writeln!(out, "    #[serde(rename = \"{}\")]", variant.name);
+                let variant_name = pascal_case(&variant.name);
+                match &variant.kind {
+                    VariantKind::Unit => {
+                        let _ = writeln!(out, "    {variant_name},");
+                    }
+                    VariantKind::Struct(fields) => {
+                        let _ = writeln!(out, "    {variant_name} {{");
+                        write_fields(out, "        ", fields, false);
+                        let _ = writeln!(out, "    }},");
+                    }
+                    VariantKind::Tuple(ty) => {
+                        let _ = writeln!(out, "    {variant_name}({}),", rust_type(ty));
+                    }
+                }
+            }
+            let _ = writeln!(out, "}}");
+        }
+        TypeDefKind::Alias(ty) => {
+            let _ = writeln!(out, "pub type {name} = {};", rust_type(ty));
+        }
+    }
+    out.push('\n');
+}
+
+/// Parameters and message construction for one client method.
+fn method_args(msg: &str, variant: &super::schema::Variant) -> (String, String) {
+    let variant_name = pascal_case(&variant.name);
+    match &variant.kind {
+        VariantKind::Unit => (String::new(), format!("{msg}::{variant_name}")),
+        VariantKind::Struct(fields) => (
+            fields
+                .iter()
+                .map(|field| format!(", {}: {}", ident(&field.name), field_type(field)))
+                .collect(),
+            format!(
+                "{msg}::{variant_name} {{ {} }}",
+                fields
+                    .iter()
+                    .map(|field| ident(&field.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        VariantKind::Tuple(ty) => (
+            format!(", msg: {}", rust_type(ty)),
+            format!("{msg}::{variant_name}(msg)"),
+        ),
+    }
+}
+
+/// Generate the message types and a typed client for a contract.
+pub fn generate(schema: &ContractSchema) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "//! Generated by `aq codegen rust` from the schema of {} {}. Do not edit.\n",
+        schema.name, schema.version
+    );
+    out.push_str("#![allow(clippy::all, dead_code, non_snake_case, unused_imports)]\n\n");
+    out.push_str("use ::aquarium::internal::{cosmwasm_std, serde, serde_json, AnyhowResult};\n");
+    out.push_str("use ::aquarium::{Executor, Querier};\n\n");
+
+    for def in schema.types.values() {
+        write_type(&mut out, def);
+    }
+
+    let client = client_name(schema);
+    let _ = writeln!(
+        out,
+        "/// Client for an instance of the {} contract.\n#[derive(Debug, Clone, PartialEq, Eq)]\npub struct {client} {{\n    pub address: String,\n}}\n",
+        schema.name
+    );
+    let _ = writeln!(out, "impl {client} {{");
+    out.push_str(
+        "    pub fn new(address: impl Into<String>) -> Self {\n        Self {\n            address: address.into(),\n        }\n    }\n",
+    );
+
+    if let Some(msg) = &schema.instantiate {
+        let msg = pascal_case(msg);
+        let _ = writeln!(
+            out,
+            "
+    /// Send a WASM instantiate, returning the transaction hash.
+    pub async fn instantiate(
+        executor: &impl Executor,
+        code_id: u64,
+        msg: &{msg},
+        funds: Vec<cosmwasm_std::Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+    ) -> AnyhowResult<String> {{
+        executor
+            .instantiate(code_id, msg, funds, label, admin, None)
+            .await
+    }}"
+        );
+    }
+
+    if let Some(msg) = &schema.migrate {
+        let msg = pascal_case(msg);
+        let _ = writeln!(
+            out,
+            "
+    /// Send a WASM migrate, returning the transaction hash.
+    pub async fn migrate(
+        &self,
+        executor: &impl Executor,
+        code_id: u64,
+        msg: &{msg},
+    ) -> AnyhowResult<String> {{
+        executor
+            .migrate(self.address.clone(), code_id, msg, None)
+            .await
+    }}"
+        );
+    }
+
+    let execute = schema.variants(&schema.execute);
+    let mut taken = vec!["new".to_string(), "instantiate".to_string()];
+    if schema.migrate.is_some() {
+        taken.push("migrate".to_string());
+    }
+    if let Some(msg) = &schema.execute {
+        let msg = pascal_case(msg);
+        for variant in execute {
+            let mut method = ident(&variant.name);
+            if taken.contains(&method) {
+                method = format!("execute_{}", variant.name);
+            }
+            taken.push(method.clone());
+            let (params, value) = method_args(&msg, variant);
+            out.push('\n');
+            write_doc(&mut out, "    ", &variant.doc);
+            let _ = writeln!(
+                out,
+                "    pub async fn {method}(
+        &self,
+        executor: &impl Executor{params},
+        funds: Vec<cosmwasm_std::Coin>,
+    ) -> AnyhowResult<String> {{
+        executor
+            .execute_smart(self.address.clone(), &{value}, funds, None)
+            .await
+    }}"
+            );
+        }
+    }
+
+    if let Some(msg) = &schema.query {
+        let msg = pascal_case(msg);
+        for variant in schema.variants(&schema.query) {
+            let mut method = ident(&variant.name);
+            if taken.contains(&method) {
+                method = format!("query_{}", variant.name);
+            }
+            taken.push(method.clone());
+            let response = schema
+                .responses
+                .get(&variant.name)
+                .map(rust_type)
+                .unwrap_or_else(|| rust_type(&Ty::Json));
+            let (params, value) = method_args(&msg, variant);
+            out.push('\n');
+            write_doc(&mut out, "    ", &variant.doc);
+            let _ = writeln!(
+                out,
+                "    pub async fn {method}(
+        &self,
+        querier: &impl Querier{params},
+    ) -> AnyhowResult<{response}> {{
+        querier.query(self.address.clone(), &{value}).await
+    }}"
+            );
+        }
+    }
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+/// Types from `cosmwasm_std` that `cosmwasm-schema` emits as definitions.
+pub const STD_TYPES: &[&str] = &[
+    "Addr",
+    "Binary",
+    "Coin",
+    "Decimal",
+    "Decimal256",
+    "Empty",
+    "Timestamp",
+    "Uint64",
+    "Uint128",
+    "Uint256",
+    "Uint512",
+];
+
+/// A type referenced by a message or response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ty {
+    String,
+    Bool,
+    /// An integer, with the JSON schema format such as `uint64`.
+    Int(String),
+    Float,
+    /// A type defined in the schema.
+    Named(String),
+    /// A type from `cosmwasm_std`, see [`STD_TYPES`].
+    Std(String),
+    Option(Box<Ty>),
+    Vec(Box<Ty>),
+    Tuple(Vec<Ty>),
+    Map(Box<Ty>),
+    Unit,
+    /// Anything the generator doesn't understand.
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    pub ty: Ty,
+    /// Fields that are not required may be omitted from the JSON.
+    pub optional: bool,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum VariantKind {
+    Unit,
+    Struct(Vec<Field>),
+    Tuple(Ty),
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: String,
+    pub kind: VariantKind,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TypeDefKind {
+    Struct(Vec<Field>),
+    Enum(Vec<Variant>),
+    Alias(Ty),
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeDef {
+    pub name: String,
+    pub kind: TypeDefKind,
+    pub doc: Option<String>,
+}
+
+/// The messages and responses of a contract, read from the JSON file emitted by
+/// `cosmwasm-schema`.
+#[derive(Debug, Clone)]
+pub struct ContractSchema {
+    pub name: String,
+    pub version: String,
+    pub instantiate: Option<String>,
+    pub execute: Option<String>,
+    pub query: Option<String>,
+    pub migrate: Option<String>,
+    /// Response types, by query variant name.
+    pub responses: BTreeMap<String, Ty>,
+    /// Every type defined by the schema, by name.
+    pub types: BTreeMap<String, TypeDef>,
+}
+
+impl ContractSchema {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let json: Value = serde_json::from_str(&contents)?;
+        Self::parse(&json)
+            .map_err(|e| anyhow::anyhow!("Could not parse schema {}: {e}", path.display()))
+    }
+
+    /// Load every contract schema in a directory, such as `contracts/vault/schema`.
+    pub fn load_dir(dir: &Path) -> Result<Vec<Self>> {
+        let mut paths = std::fs::read_dir(dir)
+            .map_err(|e| anyhow::anyhow!("Could not read schema directory {}: {e}", dir.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<PathBuf>>>()?;
+        paths.sort();
+
+        let mut schemas = vec![];
+        for path in paths {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let json: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            // Only the combined file written by `write_api!` has the contract name.
+            if json.get("contract_name").is_some() {
+                schemas.push(Self::load(&path)?);
+            }
+        }
+        if schemas.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No contract schemas found in {}, expected the JSON file written by cosmwasm-schema's write_api!",
+                dir.display()
+            ));
+        }
+        Ok(schemas)
+    }
+
+    pub fn parse(json: &Value) -> Result<Self> {
+        let mut parser = Parser::default();
+        let mut root = |key: &str| -> Result<Option<String>> {
+            match json.get(key) {
+                Some(schema) if schema.is_object() => Ok(Some(parser.root(schema)?)),
+                _ => Ok(None),
+            }
+        };
+        let instantiate = root("instantiate")?;
+        let execute = root("execute")?;
+        let query = root("query")?;
+        let migrate = root("migrate")?;
+
+        let mut responses = BTreeMap::new();
+        if let Some(Value::Object(map)) = json.get("responses") {
+            for (query, schema) in map {
+                parser.definitions(schema)?;
+                if let Some(title) = schema["title"].as_str().filter(|t| STD_TYPES.contains(t)) {
+                    responses.insert(query.clone(), Ty::Std(title.to_string()));
+                    continue;
+                }
+                let ty = match parser.parse_ty(schema)? {
+                    // Structs and enums are generated under their title.
+                    Ty::Json => Ty::Named(parser.root(schema)?),
+                    ty => ty,
+                };
+                responses.insert(query.clone(), ty);
+            }
+        }
+
+        Ok(Self {
+            name: json["contract_name"]
+                .as_str()
+                .ok_or(anyhow::anyhow!("Missing contract_name"))?
+                .to_string(),
+            version: json["contract_version"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            instantiate,
+            execute,
+            query,
+            migrate,
+            responses,
+            types: parser.types,
+        })
+    }
+
+    /// The variants of a message enum, such as the `ExecuteMsg`.
+    pub fn variants(&self, name: &Option<String>) -> &[Variant] {
+        match name.as_ref().and_then(|name| self.types.get(name)) {
+            Some(TypeDef {
+                kind: TypeDefKind::Enum(variants),
+                ..
+            }) => variants,
+            _ => &[],
+        }
+    }
+}
+
+fn doc(schema: &Value) -> Option<String> {
+    schema["description"].as_str().map(|doc| doc.to_string())
+}
+
+/// Convert a schema title or contract name such as `cw20-base` into a type name.
+pub fn pascal_case(name: &str) -> String {
+    name.split(['_', '-', ' '])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Parser {
+    types: BTreeMap<String, TypeDef>,
+}
+
+impl Parser {
+    /// Parse a root schema, defining it under its title.
+    fn root(&mut self, schema: &Value) -> Result<String> {
+        self.definitions(schema)?;
+        let name = pascal_case(
+            schema["title"]
+                .as_str()
+                .ok_or(anyhow::anyhow!("Root schema without a title"))?,
+        );
+        if !self.types.contains_key(&name) {
+            let def = self.parse_def(&name, schema)?;
+            self.types.insert(name.clone(), def);
+        }
+        Ok(name)
+    }
+
+    fn definitions(&mut self, schema: &Value) -> Result<()> {
+        if let Some(Value::Object(definitions)) = schema.get("definitions") {
+            for (name, definition) in definitions {
+                if STD_TYPES.contains(&name.as_str()) || self.types.contains_key(name) {
+                    continue;
+                }
+                let def = self.parse_def(name, definition)?;
+                self.types.insert(name.clone(), def);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_def(&mut self, name: &str, schema: &Value) -> Result<TypeDef> {
+        let kind = if let Some(Value::Object(properties)) = schema.get("properties") {
+            TypeDefKind::Struct(self.fields(properties, &schema["required"])?)
+        } else if let Some(Value::Array(variants)) =
+            schema.get("oneOf").or_else(|| schema.get("anyOf"))
+        {
+            let is_nullable = variants.iter().any(|v| v["type"] == "null");
+            if is_nullable {
+                TypeDefKind::Alias(self.parse_ty(schema)?)
+            } else {
+                let mut out = vec![];
+                for variant in variants {
+                    out.extend(self.variants(variant)?);
+                }
+                TypeDefKind::Enum(out)
+            }
+        } else if let (Some("string"), Some(Value::Array(_))) =
+            (schema["type"].as_str(), schema.get("enum"))
+        {
+            TypeDefKind::Enum(self.variants(schema)?)
+        } else if schema["type"] == "object"
+            && !matches!(schema.get("additionalProperties"), Some(Value::Object(_)))
+        {
+            TypeDefKind::Struct(vec![])
+        } else {
+            TypeDefKind::Alias(self.parse_ty(schema)?)
+        };
+        Ok(TypeDef {
+            name: name.to_string(),
+            kind,
+            doc: doc(schema),
+        })
+    }
+
+    fn fields(&mut self, properties: &Map<String, Value>, required: &Value) -> Result<Vec<Field>> {
+        let required = required
+            .as_array()
+            .map(|r| r.iter().filter_map(|r| r.as_str()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        properties
+            .iter()
+            .map(|(name, schema)| {
+                let ty = self.parse_ty(schema)?;
+                let optional = !required.contains(&name.as_str());
+                Ok(Field {
+                    name: name.clone(),
+                    ty,
+                    optional,
+                    doc: doc(schema),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse one entry of a `oneOf`, which may hold several unit variants.
+    fn variants(&mut self, schema: &Value) -> Result<Vec<Variant>> {
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            return Ok(values
+                .iter()
+                .filter_map(|value| value.as_str())
+                .map(|name| Variant {
+                    name: name.to_string(),
+                    kind: VariantKind::Unit,
+                    doc: doc(schema),
+                })
+                .collect());
+        }
+        let Some(Value::Object(properties)) = schema.get("properties") else {
+            return Err(anyhow::anyhow!("Unsupported enum variant: {schema}"));
+        };
+        let (name, payload) = properties
+            .iter()
+            .next()
+            .ok_or(anyhow::anyhow!("Enum variant without properties: {schema}"))?;
+        let kind = match payload.get("properties") {
+            Some(Value::Object(fields)) => {
+                VariantKind::Struct(self.fields(fields, &payload["required"])?)
+            }
+            _ if payload["type"] == "object" && payload.get("$ref").is_none() => {
+                VariantKind::Struct(vec![])
+            }
+            _ => VariantKind::Tuple(self.parse_ty(payload)?),
+        };
+        Ok(vec![Variant {
+            name: name.clone(),
+            kind,
+            doc: doc(schema),
+        }])
+    }
+
+    /// Map a schema to a type. Inline objects and enums without a name become [`Ty::Json`].
+    fn parse_ty(&mut self, schema: &Value) -> Result<Ty> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/definitions/");
+            return Ok(if STD_TYPES.contains(&name) {
+                Ty::Std(name.to_string())
+            } else {
+                Ty::Named(name.to_string())
+            });
+        }
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            if all.len() == 1 {
+                return self.parse_ty(&all[0]);
+            }
+        }
+        if let Some(Value::Array(any)) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+            let (nulls, others): (Vec<&Value>, Vec<&Value>) =
+                any.iter().partition(|s| s["type"] == "null");
+            if !nulls.is_empty() && others.len() == 1 {
+                return Ok(Ty::Option(Box::new(self.parse_ty(others[0])?)));
+            }
+            return Ok(Ty::Json);
+        }
+
+        let ty = match &schema["type"] {
+            Value::Array(types) => {
+                let types = types.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>();
+                match types.as_slice() {
+                    [ty, "null"] | ["null", ty] => {
+                        let mut inner = schema.clone();
+                        inner["type"] = Value::String(ty.to_string());
+                        return Ok(Ty::Option(Box::new(self.parse_ty(&inner)?)));
+                    }
+                    _ => return Ok(Ty::Json),
+                }
+            }
+            Value::String(ty) => ty.as_str(),
+            _ => return Ok(Ty::Json),
+        };
+        Ok(match ty {
+            "string" if schema.get("enum").is_none() => Ty::String,
+            "boolean" => Ty::Bool,
+            "integer" => Ty::Int(schema["format"].as_str().unwrap_or("int64").to_string()),
+            "number" => Ty::Float,
+            "null" => Ty::Unit,
+            "array" => match &schema["items"] {
+                Value::Array(items) => Ty::Tuple(
+                    items
+                        .iter()
+                        .map(|item| self.parse_ty(item))
+                        .collect::<Result<_>>()?,
+                ),
+                Value::Object(_) => Ty::Vec(Box::new(self.parse_ty(&schema["items"])?)),
+                _ => Ty::Vec(Box::new(Ty::Json)),
+            },
+            "object" => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() && schema.get("properties").is_none() => {
+                    Ty::Map(Box::new(self.parse_ty(values)?))
+                }
+                _ => Ty::Json,
+            },
+            _ => Ty::Json,
+        })
+    }
+}
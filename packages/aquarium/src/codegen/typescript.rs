@@ -0,0 +1,214 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use serde_json::{json, Map, Value};
+
+use crate::NetworkSpecificRefs;
+
+use super::schema::{pascal_case, ContractSchema, Field, Ty, TypeDef, TypeDefKind, VariantKind};
+
+/// Aliases for the `cosmwasm_std` types, which serialize as strings or simple objects.
+const STD_ALIASES: &str = "export type Addr = string;
+export type Binary = string;
+export type Decimal = string;
+export type Decimal256 = string;
+export type Timestamp = Uint64;
+export type Uint64 = string;
+export type Uint128 = string;
+export type Uint256 = string;
+export type Uint512 = string;
+export type Empty = Record<string, never>;
+export interface Coin {
+  denom: string;
+  amount: Uint128;
+}
+";
+
+/// The addresses and code IDs of a network's contracts, as exported to `contracts.json`.
+pub fn refs_json(network: &str, chain_id: Option<&str>, refs: &NetworkSpecificRefs) -> Value {
+    let contracts = refs
+        .contracts
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(name, contract)| {
+            let instances = contract
+                .instances
+                .iter()
+                .map(|instance| {
+                    let mut out = Map::new();
+                    out.insert("codeId".to_string(), json!(instance.code_id));
+                    out.insert("address".to_string(), json!(instance.address));
+                    for (key, value) in instance.attrs.iter().collect::<BTreeMap<_, _>>() {
+                        out.insert(key.clone(), value.clone());
+                    }
+                    Value::Object(out)
+                })
+                .collect::<Vec<_>>();
+            let value = json!({
+                "codeId": contract.code_ids.last(),
+                "codeIds": contract.code_ids,
+                "address": contract.instances.last().map(|instance| &instance.address),
+                "instances": instances,
+            });
+            (name.clone(), value)
+        })
+        .collect::<Map<_, _>>();
+    json!({
+        "network": network,
+        "chainId": chain_id,
+        "contracts": contracts,
+    })
+}
+
+/// A TypeScript module exporting the refs from [`refs_json`] as constants.
+pub fn refs_module(refs: &Value) -> String {
+    let mut out =
+        String::from("// Generated by `aq export ts` from contracts.json. Do not edit.\n\n");
+    let _ = writeln!(out, "export const network = {} as const;", refs["network"]);
+    let _ = writeln!(
+        out,
+        "export const chainId = {} as const;\n",
+        refs["chainId"]
+    );
+    let _ = writeln!(
+        out,
+        "export const contracts = {} as const;\n",
+        serde_json::to_string_pretty(&refs["contracts"]).unwrap_or_default()
+    );
+    out.push_str("export type ContractName = keyof typeof contracts;\n");
+    out
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: &Option<String>) {
+    if let Some(doc) = doc {
+        let _ = writeln!(out, "{indent}/**");
+        for line in doc.lines() {
+            let _ = writeln!(
+                out,
+                "{indent} *{}{line}",
+                if line.is_empty() { "" } else { " " }
+            );
+        }
+        let _ = writeln!(out, "{indent} */");
+    }
+}
+
+pub fn ts_type(ty: &Ty) -> String {
+    match ty {
+        Ty::String => "string".to_string(),
+        Ty::Bool => "boolean".to_string(),
+        Ty::Int(_) | Ty::Float => "number".to_string(),
+        Ty::Named(name) => pascal_case(name),
+        Ty::Std(name) => name.clone(),
+        Ty::Option(inner) => format!("{} | null", ts_type(inner)),
+        Ty::Vec(inner) => match inner.as_ref() {
+            Ty::Option(_) => format!("({})[]", ts_type(inner)),
+            _ => format!("{}[]", ts_type(inner)),
+        },
+        Ty::Tuple(items) => format!(
+            "[{}]",
+            items.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+        ),
+        Ty::Map(inner) => format!("Record<string, {}>", ts_type(inner)),
+        Ty::Unit => "null".to_string(),
+        Ty::Json => "unknown".to_string(),
+    }
+}
+
+fn property(name: &str) -> String {
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+    {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+fn write_fields(out: &mut String, indent: &str, fields: &[Field]) {
+    for field in fields {
+        write_doc(out, indent, &field.doc);
+        let optional = if field.optional { "?" } else { "" };
+        let ty = match (&field.ty, field.optional) {
+            (Ty::Option(_), _) | (_, false) => ts_type(&field.ty),
+            (ty, true) => format!("{} | null", ts_type(ty)),
+        };
+        let _ = writeln!(out, "{indent}{}{optional}: {ty};", property(&field.name));
+    }
+}
+
+fn write_type(out: &mut String, def: &TypeDef) {
+    write_doc(out, "", &def.doc);
+    let name = pascal_case(&def.name);
+    match &def.kind {
+        TypeDefKind::Struct(fields) if fields.is_empty() => {
+            let _ = writeln!(out, "export interface {name} {{}}");
+        }
+        TypeDefKind::Struct(fields) => {
+            let _ = writeln!(out, "export interface {name} {{");
+            write_fields(out, "  ", fields);
+            let _ = writeln!(out, "}}");
+        }
+        TypeDefKind::Enum(variants) => {
+            let _ = writeln!(out, "export type {name} =");
+            for variant in variants {
+                write_doc(out, "  ", &variant.doc);
+                match &variant.kind {
+                    VariantKind::Unit => {
+                        let _ = writeln!(out, "  | {:?}", variant.name);
+                    }
+                    VariantKind::Struct(fields) if fields.is_empty() => {
+                        let _ = writeln!(out, "  | {{ {}: {{}} }}", property(&variant.name));
+                    }
+                    VariantKind::Struct(fields) => {
+                        let _ = writeln!(out, "  | {{\n      {}: {{", property(&variant.name));
+                        write_fields(out, "        ", fields);
+                        let _ = writeln!(out, "      }};\n    }}");
+                    }
+                    VariantKind::Tuple(ty) => {
+                        let _ = writeln!(
+                            out,
+                            "  | {{ {}: {} }}",
+                            property(&variant.name),
+                            ts_type(ty)
+                        );
+                    }
+                }
+            }
+            if variants.is_empty() {
+                out.push_str("  never");
+            }
+            out.truncate(out.trim_end().len());
+            out.push_str(";\n");
+        }
+        TypeDefKind::Alias(ty) => {
+            let _ = writeln!(out, "export type {name} = {};", ts_type(ty));
+        }
+    }
+    out.push('\n');
+}
+
+/// Generate TypeScript types for a contract's messages and query responses.
+pub fn generate(schema: &ContractSchema) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Generated by `aq export ts` from the schema of {} {}. Do not edit.\n",
+        schema.name, schema.version
+    );
+    out.push_str(STD_ALIASES);
+    out.push('\n');
+
+    for def in schema.types.values() {
+        write_type(&mut out, def);
+    }
+
+    if !schema.responses.is_empty() {
+        out.push_str("/** The response to each query, by variant name. */\nexport interface QueryResponses {\n");
+        for (query, ty) in &schema.responses {
+            let _ = writeln!(out, "  {}: {};", property(query), ts_type(ty));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
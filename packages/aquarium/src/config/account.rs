@@ -1,56 +1,151 @@
-use anyhow::Result;
-use cosmrs::{
-    bip32::Mnemonic,
-    crypto::{secp256k1::SigningKey, PublicKey},
-    AccountId,
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
 };
+
+use anyhow::Result;
+use cosmrs::{crypto::PublicKey, AccountId};
 use serde::{Deserialize, Serialize};
 
+use crate::client::{
+    offline::{parse_public_key, SignMode},
+    signer::{CommandSigner, LocalSigner, RemoteSigner, Signer},
+};
+
+fn is_direct(sign_mode: &SignMode) -> bool {
+    *sign_mode == SignMode::Direct
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializableAccount {
+    #[serde(flatten)]
+    pub key: AccountKey,
+    /// A granter address this account executes contracts for through authz.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acting_as: Option<String>,
+    /// How the account signs, direct unless set to amino_json.
+    #[serde(default, skip_serializing_if = "is_direct")]
+    pub sign_mode: SignMode,
+}
+
+/// Where the key of an account lives.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
-pub enum SerializableAccount {
-    Mnemonic { mnemonic: String },
-    EnvMnemonic { env: String },
+pub enum AccountKey {
+    Mnemonic {
+        mnemonic: String,
+    },
+    EnvMnemonic {
+        env: String,
+    },
+    /// A file holding a hex private key, relative to the project root.
+    Keystore {
+        keystore: PathBuf,
+    },
+    /// A program and its arguments that sign, see [`CommandSigner`].
+    Command {
+        command: Vec<String>,
+        /// The base64 public key the command signs for.
+        public_key: String,
+    },
+    /// The URL of a signing service, see [`RemoteSigner`].
+    Remote {
+        remote: String,
+        /// The base64 public key the service signs for.
+        public_key: String,
+    },
 }
 
 impl SerializableAccount {
-    pub fn get_keypair(&self, derivation_path: impl AsRef<str>) -> Result<(PublicKey, SigningKey)> {
-        let mnemonic = match self {
-            Self::Mnemonic { mnemonic } => mnemonic.clone(),
-            Self::EnvMnemonic { env } => std::env::var(env).map_err(|e| anyhow::anyhow!(e))?,
-        };
-        let seed = Mnemonic::new(mnemonic, Default::default())?.to_seed("");
-        let sk = SigningKey::derive_from_path(&seed, &derivation_path.as_ref().parse()?)?;
-        let pk = sk.public_key();
-        Ok((pk, sk))
+    pub fn acting_as(&self) -> Option<&str> {
+        self.acting_as.as_deref()
+    }
+
+    pub fn sign_mode(&self) -> SignMode {
+        self.sign_mode
+    }
+
+    /// The signer of the account, deriving mnemonics at `derivation_path` and reading keystores
+    /// relative to `root`.
+    pub fn signer(&self, derivation_path: impl AsRef<str>, root: &Path) -> Result<Arc<dyn Signer>> {
+        Ok(match &self.key {
+            AccountKey::Mnemonic { mnemonic } => Arc::new(LocalSigner::from_mnemonic(
+                mnemonic,
+                derivation_path.as_ref(),
+            )?),
+            AccountKey::EnvMnemonic { env } => {
+                let mnemonic = std::env::var(env).map_err(|e| anyhow::anyhow!(e))?;
+                Arc::new(LocalSigner::from_mnemonic(
+                    &mnemonic,
+                    derivation_path.as_ref(),
+                )?)
+            }
+            AccountKey::Keystore { keystore } => {
+                Arc::new(LocalSigner::from_keystore(&root.join(keystore))?)
+            }
+            AccountKey::Command {
+                command,
+                public_key,
+            } => Arc::new(CommandSigner::new(command, parse_public_key(public_key)?)?),
+            AccountKey::Remote { remote, public_key } => {
+                Arc::new(RemoteSigner::new(remote, parse_public_key(public_key)?))
+            }
+        })
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct AccountWithInfo {
-    inner: SerializableAccount,
-    pub derivation_path: String,
+    signer: Arc<dyn Signer>,
     pub address: AccountId,
+    /// The granter whose contracts `execute_smart` executes through authz, if any.
+    pub acting_as: Option<String>,
+    /// How transactions are signed unless overridden per transaction.
+    pub sign_mode: SignMode,
 }
 
 impl AccountWithInfo {
     pub fn new(
         account: SerializableAccount,
         derivation_path: impl AsRef<str>,
+        root: &Path,
         prefix: impl AsRef<str>,
     ) -> Result<Self> {
-        let (pk, _) = account.get_keypair(&derivation_path)?;
-        let address = pk
+        Ok(Self {
+            acting_as: account.acting_as().map(str::to_string),
+            sign_mode: account.sign_mode(),
+            ..Self::with_signer(account.signer(derivation_path, root)?, prefix)?
+        })
+    }
+
+    /// An account signing with `signer`, such as one a script builds itself.
+    pub fn with_signer(signer: Arc<dyn Signer>, prefix: impl AsRef<str>) -> Result<Self> {
+        let address = signer
+            .public_key()
             .account_id(prefix.as_ref())
             .map_err(|e| anyhow::anyhow!(e))?;
         Ok(Self {
-            inner: account,
-            derivation_path: derivation_path.as_ref().to_string(),
+            signer,
             address,
+            acting_as: None,
+            sign_mode: SignMode::Direct,
         })
     }
 
-    pub fn get_keypair(&self) -> Result<(PublicKey, SigningKey)> {
-        self.inner.get_keypair(&self.derivation_path)
+    pub fn public_key(&self) -> PublicKey {
+        self.signer.public_key()
+    }
+
+    pub fn signer(&self) -> &dyn Signer {
+        self.signer.as_ref()
     }
 }
+
+/// A legacy amino multisig whose members sign offline with `aq tx`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigAccount {
+    pub threshold: u32,
+    /// Accounts in `Aquarium.toml` or base64 secp256k1 public keys, in the order the multisig
+    /// was created with.
+    pub members: Vec<String>,
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The local chain run by `aq devnet`, defined in the `[devnet]` table of `Aquarium.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevnetConfig {
+    /// The chain binary, such as `kujirad` or `wasmd`, looked up on `PATH`.
+    #[serde(default = "default_binary")]
+    pub binary: String,
+    /// The network in `Aquarium.toml` the chain serves, for its chain ID, denom and LCD port.
+    #[serde(default = "default_network")]
+    pub network: String,
+    /// The chain's home directory, relative to the project root.
+    #[serde(default = "default_home")]
+    pub home: PathBuf,
+    /// The genesis balance of each configured account, in the network's gas denom.
+    #[serde(default = "default_balance")]
+    pub balance: u128,
+    /// The amount the genesis validator bonds.
+    #[serde(default = "default_stake")]
+    pub stake: u128,
+}
+
+fn default_binary() -> String {
+    "kujirad".to_string()
+}
+
+fn default_network() -> String {
+    "devnet".to_string()
+}
+
+fn default_home() -> PathBuf {
+    PathBuf::from(".aquarium/devnet")
+}
+
+fn default_balance() -> u128 {
+    1_000_000_000_000
+}
+
+fn default_stake() -> u128 {
+    100_000_000_000
+}
+
+impl Default for DevnetConfig {
+    fn default() -> Self {
+        Self {
+            binary: default_binary(),
+            network: default_network(),
+            home: default_home(),
+            balance: default_balance(),
+            stake: default_stake(),
+        }
+    }
+}
@@ -1,20 +1,31 @@
 use std::path::PathBuf;
 
-use crate::{ContractRefs, NetworkSpecificRefs, QueryClient, SigningClient};
+use cosmwasm_std::Coin;
 
-pub struct Env {
+use crate::{
+    utils::ensure_tx_success, ContractRefs, DynExecutor, DynQuerier, Executor, NetworkSpecificRefs,
+    Querier,
+};
+
+/// The network, clients and contract refs a task runs with.
+///
+/// By default the clients are trait objects, so tasks run unchanged against the LCD or
+/// another backend such as [`MockChain`](crate::mock::MockChain).
+pub struct Env<Q = Box<dyn DynQuerier>, E = Box<dyn DynExecutor>> {
     pub network: String,
-    pub querier: QueryClient,
-    pub executor: SigningClient,
-    refs_path: PathBuf,
+    pub querier: Q,
+    pub executor: E,
+    /// Sends the transfers made by [`Env::fund`], if the network has a faucet.
+    pub faucet: Option<E>,
+    refs_path: Option<PathBuf>,
     pub refs: NetworkSpecificRefs,
 }
 
-impl Env {
+impl<Q, E> Env<Q, E> {
     pub fn new(
         network: String,
-        querier: QueryClient,
-        executor: SigningClient,
+        querier: Q,
+        executor: E,
         refs: NetworkSpecificRefs,
         refs_path: PathBuf,
     ) -> Result<Self, anyhow::Error> {
@@ -22,16 +33,55 @@ impl Env {
             network,
             querier,
             executor,
+            faucet: None,
             refs,
-            refs_path,
+            refs_path: Some(refs_path),
         })
     }
 
+    /// An environment whose refs are only kept in memory, [`Env::save_refs`] does nothing.
+    pub fn in_memory(network: String, querier: Q, executor: E, refs: NetworkSpecificRefs) -> Self {
+        Self {
+            network,
+            querier,
+            executor,
+            faucet: None,
+            refs,
+            refs_path: None,
+        }
+    }
+
+    /// Fund accounts from `faucet` with [`Env::fund`].
+    pub fn with_faucet(mut self, faucet: E) -> Self {
+        self.faucet = Some(faucet);
+        self
+    }
+
     pub fn save_refs(&self) -> Result<(), anyhow::Error> {
-        let mut crefs = ContractRefs::load_or_default(self.refs_path.clone())?;
+        let Some(refs_path) = &self.refs_path else {
+            return Ok(());
+        };
+        let mut crefs = ContractRefs::load_or_default(refs_path.clone())?;
         crefs
             .networks
             .insert(self.network.clone(), self.refs.clone());
-        crefs.save(self.refs_path.clone())
+        crefs.save(refs_path.clone())
+    }
+}
+
+impl<Q, E: Executor + Querier + Sync> Env<Q, E> {
+    /// Send `coins` from the faucet to `address` and wait for the transfer, returning its hash.
+    pub async fn fund(
+        &self,
+        address: impl Into<String>,
+        coins: Vec<Coin>,
+    ) -> Result<String, anyhow::Error> {
+        let faucet = self.faucet.as_ref().ok_or(anyhow::anyhow!(
+            "No faucet account configured for network \"{}\"",
+            self.network
+        ))?;
+        let hash = faucet.bank_send(address.into(), coins, None).await?;
+        ensure_tx_success(&faucet.wait_for_transaction(hash.clone()).await?)?;
+        Ok(hash)
     }
 }
@@ -1,4 +1,6 @@
 pub mod account;
+pub mod devnet;
 pub mod env;
 pub mod network;
+pub mod pipeline;
 pub mod refs;
\ No newline at end of file
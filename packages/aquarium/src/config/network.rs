@@ -1,23 +1,151 @@
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cosmwasm_std::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use anyhow::Result;
 
+use crate::{
+    utils::{parse_coins, parse_dec_coin},
+    HttpTransport, Transport,
+};
+
+/// The price of a unit of gas, `"0.00125ukuji"` or `"auto"` in `Aquarium.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GasPrice {
+    /// A fixed price, with the denom it was written with, which must be the gas denom.
+    Fixed {
+        amount: Decimal,
+        denom: Option<String>,
+    },
+    /// Queried before every transaction, from the feemarket module's base fee or else the
+    /// node's minimum gas price.
+    Auto,
+}
+
+impl Serialize for GasPrice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Fixed { amount, denom } => serializer
+                .serialize_str(&format!("{amount}{}", denom.as_deref().unwrap_or_default())),
+            Self::Auto => serializer.serialize_str("auto"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GasPrice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::from(DecimalOrNumber::deserialize(deserializer)?).as_str() {
+            "auto" => Ok(Self::Auto),
+            price => {
+                let (amount, denom) = parse_dec_coin(price).map_err(serde::de::Error::custom)?;
+                Ok(Self::Fixed { amount, denom })
+            }
+        }
+    }
+}
+
+/// A decimal written as a string or, in older configs, a float.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DecimalOrNumber {
+    Text(String),
+    Number(f64),
+}
+
+impl From<DecimalOrNumber> for String {
+    fn from(value: DecimalOrNumber) -> Self {
+        match value {
+            DecimalOrNumber::Text(text) => text,
+            // Floats display without an exponent, in their shortest exact form.
+            DecimalOrNumber::Number(number) => number.to_string(),
+        }
+    }
+}
+
+fn decimal<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    let text = String::from(DecimalOrNumber::deserialize(deserializer)?);
+    Decimal::from_str(&text).map_err(serde::de::Error::custom)
+}
+
+fn optional_decimal<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Decimal>, D::Error> {
+    Option::<DecimalOrNumber>::deserialize(deserializer)?
+        .map(|value| Decimal::from_str(&String::from(value)).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Network {
     pub chain_id: String,
     pub lcd_addr: String,
-    pub gas_price: f64,
-    pub gas_adjustment: f64,
+    pub gas_price: GasPrice,
+    /// The highest gas price an `"auto"` gas price may reach before transactions are rejected.
+    #[serde(
+        default,
+        deserialize_with = "optional_decimal",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_gas_price: Option<Decimal>,
+    #[serde(deserialize_with = "decimal")]
+    pub gas_adjustment: Decimal,
     pub gas_denom: String,
     pub account_prefix: String,
+    /// The account in `Aquarium.toml` that funds other accounts on this network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faucet: Option<String>,
+    /// The admin expected of this network's contracts by `aq contract audit`, such as a
+    /// multisig, as an account in `Aquarium.toml` or an address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin: Option<String>,
+    /// Whether this network holds real funds, so uploads that anyone could instantiate warn.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub mainnet: bool,
+    /// The most a transaction may pay in fees on this network, such as `"5000000ukuji"`.
+    /// Fees above it, or in a denom it doesn't list, are rejected before signing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<String>,
+    /// How LCD requests are sent, over HTTP if unset.
+    #[serde(skip)]
+    pub transport: Option<Arc<dyn Transport>>,
 }
 
 impl Network {
+    /// Send LCD requests through `transport`, such as to record or replay them.
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Fail if `fee` is above this network's `max_fee`.
+    pub fn check_fee(&self, fee: &cosmwasm_std::Coin) -> Result<()> {
+        let Some(max_fee) = &self.max_fee else {
+            return Ok(());
+        };
+        match parse_coins(max_fee)?
+            .into_iter()
+            .find(|max| max.denom == fee.denom)
+        {
+            Some(max) if fee.amount <= max.amount => Ok(()),
+            Some(max) => Err(anyhow::anyhow!(
+                "Fee of {fee} is above the maximum fee of {max} on {}",
+                self.chain_id
+            )),
+            None => Err(anyhow::anyhow!(
+                "Fee of {fee} is in a denom not allowed by the maximum fee of {max_fee} on {}",
+                self.chain_id
+            )),
+        }
+    }
+
+    fn transport(&self) -> &dyn Transport {
+        self.transport.as_deref().unwrap_or(&HttpTransport)
+    }
+
     pub async fn get(&self, path: impl AsRef<str>) -> Result<serde_json::Value> {
-        let client = Client::new();
-        let path = format!("{}/{}", self.lcd_addr, path.as_ref());
-        Ok(client.get(path).send().await?.json().await?)
+        self.transport().get(&self.lcd_addr, path.as_ref()).await
     }
 
     pub async fn post(
@@ -25,9 +153,85 @@ impl Network {
         path: impl AsRef<str>,
         body: impl Serialize,
     ) -> Result<serde_json::Value> {
-        let client = Client::new();
-        let path = format!("{}/{}", self.lcd_addr, path.as_ref());
-        Ok(client.post(path).json(&body).send().await?.json().await?)
+        self.transport()
+            .post(&self.lcd_addr, path.as_ref(), serde_json::to_value(body)?)
+            .await
+    }
+
+    /// The gas price transactions pay in the gas denom, querying the chain if it is `"auto"`.
+    pub async fn current_gas_price(&self) -> Result<Decimal> {
+        let price = match &self.gas_price {
+            GasPrice::Fixed { amount, denom } => {
+                return match denom {
+                    Some(denom) if *denom != self.gas_denom => Err(anyhow::anyhow!(
+                        "The gas price of {} is in {denom}, but its gas denom is {}",
+                        self.chain_id,
+                        self.gas_denom
+                    )),
+                    _ => Ok(*amount),
+                }
+            }
+            GasPrice::Auto => match self.feemarket_gas_price().await {
+                Ok(price) => price,
+                Err(_) => self.minimum_gas_price().await?,
+            },
+        };
+        match self.max_gas_price {
+            Some(max) if price > max => Err(anyhow::anyhow!(
+                "Gas price {price}{} on {} is above max_gas_price {max}",
+                self.gas_denom,
+                self.chain_id
+            )),
+            _ => Ok(price),
+        }
+    }
+
+    async fn feemarket_gas_price(&self) -> Result<Decimal> {
+        let response = self
+            .get(format!("feemarket/v1/gas_price/{}", self.gas_denom))
+            .await?;
+        let amount = response["price"]["amount"]
+            .as_str()
+            .ok_or(anyhow::anyhow!("Error parsing feemarket gas price"))?;
+        Ok(Decimal::from_str(amount)?)
+    }
+
+    async fn minimum_gas_price(&self) -> Result<Decimal> {
+        let response = self.get("cosmos/base/node/v1beta1/config").await?;
+        let prices = response["minimum_gas_price"]
+            .as_str()
+            .ok_or(anyhow::anyhow!(
+                "Could not query the gas price on {}, neither the feemarket module nor the node config are available",
+                self.chain_id
+            ))?;
+        prices
+            .split(',')
+            .filter_map(|price| parse_dec_coin(price).ok())
+            .find_map(|(amount, denom)| (denom.as_ref() == Some(&self.gas_denom)).then_some(amount))
+            .ok_or(anyhow::anyhow!(
+                "The node on {} has no minimum gas price in {}",
+                self.chain_id,
+                self.gas_denom
+            ))
+    }
+
+    /// Broadcast a signed transaction, returning its hash.
+    pub async fn broadcast(&self, tx_bytes: &[u8]) -> Result<String> {
+        let post_data = serde_json::json!({
+            "tx_bytes": STANDARD.encode(tx_bytes),
+            "mode": "BROADCAST_MODE_SYNC",
+        });
+
+        let res = self.post("cosmos/tx/v1beta1/txs", &post_data).await?;
+
+        if res["tx_response"]["code"].as_u64() == Some(11) {
+            return Err(anyhow::anyhow!("Insufficient gas"));
+        }
+
+        Ok(res["tx_response"]["txhash"]
+            .as_str()
+            .ok_or(anyhow::anyhow!("Error parsing txhash. Response: {res:#?}"))?
+            .to_string())
     }
 
     pub async fn current_block_height(&self) -> Result<u32> {
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// A named sequence of tasks, defined in the `[pipelines]` table of `Aquarium.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pipeline {
+    #[serde(default)]
+    pub about: String,
+    pub steps: Vec<PipelineStep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PipelineStep {
+    Task(String),
+    WithArgs {
+        task: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl PipelineStep {
+    pub fn task(&self) -> &str {
+        match self {
+            Self::Task(task) => task,
+            Self::WithArgs { task, .. } => task,
+        }
+    }
+
+    pub fn args(&self) -> &[String] {
+        match self {
+            Self::Task(_) => &[],
+            Self::WithArgs { args, .. } => args,
+        }
+    }
+}
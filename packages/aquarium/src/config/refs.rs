@@ -1,4 +1,7 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +22,9 @@ pub struct NetworkSpecificRefs {
 pub struct Contract {
     pub code_ids: Vec<u64>,
     pub instances: Vec<ContractInstance>,
+    /// Hex encoded SHA-256 checksums of stored code, by code ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checksums: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +87,16 @@ impl NetworkSpecificRefs {
     }
 }
 
+impl Contract {
+    pub fn checksum(&self, code_id: u64) -> Option<&String> {
+        self.checksums.get(&code_id.to_string())
+    }
+
+    pub fn set_checksum(&mut self, code_id: u64, checksum: String) {
+        self.checksums.insert(code_id.to_string(), checksum);
+    }
+}
+
 impl ContractInstance {
     pub fn new(code_id: u64, address: String) -> Self {
         Self {
@@ -0,0 +1,216 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{client::messages::AccessConfig, NetworkSpecificRefs};
+
+/// A declarative description of the contracts to deploy, read from `deploy.toml`,
+/// `deploy.yaml` or `deploy.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub contracts: Vec<ManifestContract>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestContract {
+    /// The name of the contract in `contracts.json`.
+    pub name: String,
+    /// Path to the wasm bytecode, relative to the manifest.
+    pub wasm: PathBuf,
+    /// Who may instantiate the code once stored, the chain's default if unset. Addresses may
+    /// contain `${...}` placeholders.
+    pub instantiate_permission: Option<AccessConfig>,
+    #[serde(default)]
+    pub instances: Vec<ManifestInstance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestInstance {
+    /// The contract label, also used to find the instance in `contracts.json`.
+    pub label: String,
+    /// The instantiate message. Strings may contain `${...}` placeholders.
+    pub msg: Value,
+    /// The admin to set. Without it, the admin of an existing instance is left as is.
+    pub admin: Option<String>,
+    /// Clear the admin of an existing instance, so it can never be migrated again.
+    #[serde(default)]
+    pub clear_admin: bool,
+    /// Funds sent with the instantiation, such as `"1000000ukuji"`.
+    #[serde(default)]
+    pub funds: String,
+    /// The migrate message used when the instance runs an older code ID than the manifest.
+    pub migrate_msg: Option<Value>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Could not read manifest {}: {e}", path.display()))?;
+        let mut manifest: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&contents).map_err(|e| anyhow::anyhow!(e))?,
+            Some("yaml") | Some("yml") => {
+                serde_yaml::from_str(&contents).map_err(|e| anyhow::anyhow!(e))?
+            }
+            Some("json") => serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!(e))?,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported manifest format, expected a .toml, .yaml or .json file"
+                ))
+            }
+        };
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for contract in &mut manifest.contracts {
+            contract.wasm = dir.join(&contract.wasm);
+            if let Some(instance) = contract
+                .instances
+                .iter()
+                .find(|instance| instance.clear_admin && instance.admin.is_some())
+            {
+                return Err(anyhow::anyhow!(
+                    "{} \"{}\" sets both admin and clear_admin",
+                    contract.name,
+                    instance.label
+                ));
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+/// Placeholder value for refs that are only known once a plan is applied.
+pub const KNOWN_AFTER_APPLY: &str = "(known after apply)";
+
+/// Contracts whose refs change when a plan is applied.
+#[derive(Debug, Clone, Default)]
+pub struct Pending {
+    pub code: HashSet<String>,
+    pub instances: HashSet<String>,
+}
+
+/// Resolves `${...}` placeholders in manifest values.
+///
+/// Supported placeholders are `${account}` for the deployer address, `${env.VAR}` for
+/// environment variables, and `${refs.<contract>.<field>}` where the field is `code_id`,
+/// `address`, or any attribute of the latest instance of the contract.
+pub struct Interpolator<'a> {
+    pub refs: &'a NetworkSpecificRefs,
+    pub account: &'a str,
+    /// When planning, refs of these contracts resolve to [`KNOWN_AFTER_APPLY`].
+    pub pending: Option<&'a Pending>,
+}
+
+impl Interpolator<'_> {
+    /// Resolve a single placeholder expression, without the surrounding `${}`.
+    pub fn resolve(&self, expr: &str) -> Result<Value> {
+        let parts = expr.trim().split('.').collect::<Vec<_>>();
+        match parts.as_slice() {
+            ["account"] => Ok(Value::String(self.account.to_string())),
+            ["env", var] => std::env::var(var)
+                .map(Value::String)
+                .map_err(|_| anyhow::anyhow!("Environment variable \"{var}\" is not set")),
+            ["refs", contract, field] => {
+                if let Some(pending) = self.pending {
+                    let changes = match *field {
+                        "code_id" => pending.code.contains(*contract),
+                        _ => pending.instances.contains(*contract),
+                    };
+                    if changes {
+                        return Ok(Value::String(KNOWN_AFTER_APPLY.to_string()));
+                    }
+                }
+                let refs = self
+                    .refs
+                    .contracts
+                    .get(*contract)
+                    .ok_or(anyhow::anyhow!("No refs for contract \"{contract}\""))?;
+                let value = match *field {
+                    "code_id" => refs.code_ids.last().map(|id| Value::from(*id)),
+                    "address" => refs
+                        .instances
+                        .last()
+                        .map(|instance| Value::String(instance.address.clone())),
+                    attr => refs
+                        .instances
+                        .last()
+                        .and_then(|instance| instance.attrs.get(attr).cloned()),
+                };
+                value.ok_or(anyhow::anyhow!(
+                    "No {field} found for contract \"{contract}\""
+                ))
+            }
+            _ => Err(anyhow::anyhow!("Unknown placeholder \"${{{expr}}}\"")),
+        }
+    }
+
+    /// Interpolate a string. A string that is a single placeholder keeps the type of its value,
+    /// so `"${refs.vault.code_id}"` becomes a number.
+    pub fn string(&self, s: &str) -> Result<Value> {
+        if let Some(expr) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+            if !expr.contains("${") {
+                return self.resolve(expr);
+            }
+        }
+
+        let mut out = String::new();
+        let mut rest = s;
+        while let Some(start) = rest.find("${") {
+            let end = rest[start..]
+                .find('}')
+                .ok_or(anyhow::anyhow!("Unterminated placeholder in \"{s}\""))?;
+            out.push_str(&rest[..start]);
+            match self.resolve(&rest[start + 2..start + end])? {
+                Value::String(value) => out.push_str(&value),
+                value => out.push_str(&value.to_string()),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        out.push_str(rest);
+        Ok(Value::String(out))
+    }
+
+    /// Interpolate every string in a JSON value.
+    pub fn value(&self, value: &Value) -> Result<Value> {
+        Ok(match value {
+            Value::String(s) => self.string(s)?,
+            Value::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.value(value))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| Ok((key.clone(), self.value(value)?)))
+                    .collect::<Result<_>>()?,
+            ),
+            value => value.clone(),
+        })
+    }
+
+    /// Interpolate a value that must be a string, such as an admin address.
+    pub fn text(&self, s: &str) -> Result<String> {
+        match self.string(s)? {
+            Value::String(s) => Ok(s),
+            value => Ok(value.to_string()),
+        }
+    }
+
+    /// Interpolate the addresses of an instantiate permission.
+    pub fn access(&self, access: &AccessConfig) -> Result<AccessConfig> {
+        Ok(match access {
+            AccessConfig::AnyOfAddresses(addresses) => AccessConfig::AnyOfAddresses(
+                addresses
+                    .iter()
+                    .map(|address| self.text(address))
+                    .collect::<Result<_>>()?,
+            ),
+            access => access.clone(),
+        })
+    }
+}
@@ -0,0 +1,255 @@
+use anyhow::Result;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    utils::{ensure_tx_success, parse_code_id, parse_coins, parse_instantiated_address},
+    ContractInstance, Env, Executor, Querier,
+};
+
+pub mod manifest;
+pub mod plan;
+
+pub use manifest::{
+    Interpolator, Manifest, ManifestContract, ManifestInstance, Pending, KNOWN_AFTER_APPLY,
+};
+pub use plan::{plan, Action, CodeRef, Plan};
+
+use plan::plan_contract;
+
+/// Attribute of a [`ContractInstance`] holding the label it was instantiated with.
+pub const LABEL_ATTR: &str = "label";
+
+/// Lowercase hex encoded SHA-256 checksum of wasm bytecode.
+pub fn checksum(bytecode: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytecode))
+}
+
+/// Apply a manifest, storing, instantiating and migrating only what differs from
+/// `contracts.json` and the chain.
+pub async fn apply(env: &mut Env, manifest: &Manifest) -> Result<()> {
+    for contract in &manifest.contracts {
+        // Plan each contract right before applying it, so refs to earlier contracts resolve.
+        let actions = plan_contract(env, contract, &mut Pending::default()).await?;
+        if actions.is_empty() {
+            println!("= {}: up to date", contract.name);
+        }
+        let mut stored = None;
+        for action in actions {
+            execute(env, action, &mut stored).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The code ID in `contracts.json` whose checksum matches `checksum`, if the latest one does.
+/// Checksums missing from `contracts.json` are queried, without being saved.
+pub async fn stored_code_id(env: &Env, name: &str, checksum: &str) -> Result<Option<u64>> {
+    let Some(contract) = env.refs.contracts.get(name) else {
+        return Ok(None);
+    };
+    let Some(code_id) = contract.code_ids.last().copied() else {
+        return Ok(None);
+    };
+    let stored = match contract.checksum(code_id) {
+        Some(stored) => stored.clone(),
+        None => env.querier.code_info(code_id).await?.checksum,
+    };
+    Ok((stored == checksum).then_some(code_id))
+}
+
+/// The latest instance of a contract in `contracts.json` with the given label.
+pub fn find_instance<'a>(env: &'a Env, name: &str, label: &str) -> Option<&'a ContractInstance> {
+    env.refs.contracts.get(name).and_then(|contract| {
+        contract
+            .instances
+            .iter()
+            .rev()
+            .find(|instance| instance.attrs.get(LABEL_ATTR).and_then(Value::as_str) == Some(label))
+    })
+}
+
+async fn execute(env: &mut Env, action: Action, stored: &mut Option<u64>) -> Result<()> {
+    let code_id = |code: CodeRef, stored: &Option<u64>| match code {
+        CodeRef::Existing(code_id) => Ok(code_id),
+        CodeRef::New => stored.ok_or(anyhow::anyhow!("Code was not stored")),
+    };
+
+    match action {
+        Action::Store {
+            contract,
+            wasm,
+            checksum,
+            access,
+            ..
+        } => {
+            let bytecode = std::fs::read(&wasm)?;
+            // Addresses may refer to contracts instantiated earlier in the apply.
+            let account = env.executor.address();
+            let access = access
+                .map(|access| {
+                    Interpolator {
+                        refs: &env.refs,
+                        account: &account,
+                        pending: None,
+                    }
+                    .access(&access)
+                })
+                .transpose()?;
+            let hash = match access {
+                Some(access) => {
+                    env.executor
+                        .store_code_with_access(bytecode, access, None)
+                        .await?
+                }
+                None => env.executor.store_code(bytecode, None).await?,
+            };
+            println!("Waiting for storecode hash {hash}");
+            let receipt = env.executor.wait_for_transaction(hash).await?;
+            ensure_tx_success(&receipt)?;
+            let code_id = parse_code_id(&receipt)?;
+            env.refs.add_code_id(&contract, code_id);
+            env.refs
+                .contracts
+                .entry(contract.clone())
+                .or_default()
+                .set_checksum(code_id, checksum);
+            env.save_refs()?;
+            *stored = Some(code_id);
+            println!("+ {contract}: stored code {code_id}");
+        }
+        Action::Instantiate {
+            contract,
+            instance,
+            code,
+            ..
+        } => {
+            let code_id = code_id(code, stored)?;
+            let account = env.executor.address();
+            let interpolator = Interpolator {
+                refs: &env.refs,
+                account: &account,
+                pending: None,
+            };
+            let msg = interpolator.value(&instance.msg)?;
+            let admin = instance
+                .admin
+                .as_deref()
+                .map(|admin| interpolator.text(admin))
+                .transpose()?;
+            let funds = parse_coins(&interpolator.text(&instance.funds)?)?;
+
+            let hash = env
+                .executor
+                .instantiate(
+                    code_id,
+                    &msg,
+                    funds,
+                    Some(instance.label.clone()),
+                    admin,
+                    None,
+                )
+                .await?;
+            println!("Waiting for instantiate hash {hash}");
+            let receipt = env.executor.wait_for_transaction(hash).await?;
+            ensure_tx_success(&receipt)?;
+            let address = parse_instantiated_address(&receipt)?;
+
+            let mut new_instance = ContractInstance::new(code_id, address.clone());
+            new_instance.attrs.insert(
+                LABEL_ATTR.to_string(),
+                Value::String(instance.label.clone()),
+            );
+            env.refs.add_contract_instance(&contract, new_instance);
+            env.save_refs()?;
+            println!(
+                "+ {contract} ({}): instantiated code {code_id} at {address}",
+                instance.label
+            );
+        }
+        Action::Migrate {
+            contract,
+            label,
+            address,
+            to,
+            msg,
+            ..
+        } => {
+            let code_id = code_id(to, stored)?;
+            // Refs of contracts changed earlier in the apply are only known now.
+            let account = env.executor.address();
+            let msg = Interpolator {
+                refs: &env.refs,
+                account: &account,
+                pending: None,
+            }
+            .value(&msg)?;
+            let hash = env
+                .executor
+                .migrate(address.clone(), code_id, &msg, None)
+                .await?;
+            println!("Waiting for migrate hash {hash}");
+            let receipt = env.executor.wait_for_transaction(hash).await?;
+            ensure_tx_success(&receipt)?;
+            if let Some(instance) = env.refs.get_contract_instance(&contract, &address) {
+                instance.code_id = code_id;
+            }
+            env.save_refs()?;
+            println!("~ {contract} ({label}): migrated {address} to code {code_id}");
+        }
+        Action::UpdateAdmin {
+            contract,
+            label,
+            address,
+            from,
+            to,
+            ..
+        } => {
+            let account = env.executor.address();
+            let to = to
+                .as_deref()
+                .map(|admin| {
+                    Interpolator {
+                        refs: &env.refs,
+                        account: &account,
+                        pending: None,
+                    }
+                    .text(admin)
+                })
+                .transpose()?;
+            if to == from {
+                println!(
+                    "= {contract} ({label}): admin is already {}",
+                    to.as_deref().unwrap_or("(none)")
+                );
+                return Ok(());
+            }
+            let hash = match &to {
+                Some(admin) => {
+                    env.executor
+                        .update_admin(address.clone(), admin.clone(), None)
+                        .await?
+                }
+                None => env.executor.clear_admin(address.clone(), None).await?,
+            };
+            println!("Waiting for admin update hash {hash}");
+            let receipt = env.executor.wait_for_transaction(hash).await?;
+            ensure_tx_success(&receipt)?;
+            println!(
+                "~ {contract} ({label}): admin of {address} set to {}",
+                to.as_deref().unwrap_or("(none)")
+            );
+        }
+        Action::Drift {
+            contract,
+            label,
+            message,
+        } => {
+            println!("! {contract} ({label}): {message}");
+        }
+        Action::Warning { contract, message } => {
+            println!("! {contract}: {message}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,346 @@
+use std::{fmt, path::PathBuf};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::{
+    client::messages::{open_access_warning, AccessConfig},
+    ContractNotFound, Env, Executor, Querier,
+};
+
+use super::{
+    checksum, find_instance,
+    manifest::{Interpolator, Manifest, ManifestContract, ManifestInstance, Pending},
+    stored_code_id, KNOWN_AFTER_APPLY,
+};
+
+/// The code an instance should run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeRef {
+    Existing(u64),
+    /// Code stored earlier in the same plan.
+    New,
+}
+
+impl fmt::Display for CodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Existing(code_id) => write!(f, "{code_id}"),
+            Self::New => write!(f, "{KNOWN_AFTER_APPLY}"),
+        }
+    }
+}
+
+/// A change needed to bring the chain in line with a manifest.
+#[derive(Debug, Clone)]
+pub enum Action {
+    Store {
+        contract: String,
+        wasm: PathBuf,
+        checksum: String,
+        /// Who may instantiate the code from the manifest, the chain's default if `None`.
+        /// Interpolated when it is sent.
+        access: Option<AccessConfig>,
+        /// The access as it would be sent, for display.
+        preview: Option<AccessConfig>,
+    },
+    Instantiate {
+        contract: String,
+        instance: ManifestInstance,
+        code: CodeRef,
+        /// The instantiate message, admin and funds as they would be sent, for display.
+        preview: (Value, Option<String>, String),
+    },
+    Migrate {
+        contract: String,
+        label: String,
+        address: String,
+        from: u64,
+        to: CodeRef,
+        /// The migrate message from the manifest, interpolated when it is sent.
+        msg: Value,
+        /// The migrate message as it would be sent, for display.
+        preview: Value,
+    },
+    UpdateAdmin {
+        contract: String,
+        label: String,
+        address: String,
+        from: Option<String>,
+        /// The admin from the manifest, interpolated when it is sent. `None` clears the
+        /// admin, which the manifest asks for with `clear_admin`.
+        to: Option<String>,
+        /// The admin as it would be set, for display.
+        preview: Option<String>,
+    },
+    /// A difference that can't be fixed by applying the manifest.
+    Drift {
+        contract: String,
+        label: String,
+        message: String,
+    },
+    /// A change that applies, but deserves a second look.
+    Warning { contract: String, message: String },
+}
+
+fn admin_display(admin: &Option<String>) -> &str {
+    admin.as_deref().unwrap_or("(none)")
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Store {
+                contract,
+                wasm,
+                checksum,
+                preview,
+                ..
+            } => {
+                writeln!(f, "  + store {contract}")?;
+                writeln!(f, "      wasm:        {}", wasm.display())?;
+                writeln!(f, "      checksum:    {checksum}")?;
+                match preview {
+                    Some(access) => writeln!(f, "      instantiate: {access}"),
+                    None => writeln!(f, "      instantiate: (chain default)"),
+                }
+            }
+            Self::Instantiate {
+                contract,
+                instance,
+                code,
+                preview: (msg, admin, funds),
+            } => {
+                writeln!(f, "  + instantiate {contract} \"{}\"", instance.label)?;
+                writeln!(f, "      code:  {code}")?;
+                writeln!(f, "      admin: {}", admin_display(admin))?;
+                if !funds.is_empty() {
+                    writeln!(f, "      funds: {funds}")?;
+                }
+                writeln!(f, "      msg:   {msg}")
+            }
+            Self::Migrate {
+                contract,
+                label,
+                address,
+                from,
+                to,
+                preview,
+                ..
+            } => {
+                writeln!(f, "  ~ migrate {contract} \"{label}\" at {address}")?;
+                writeln!(f, "      code: {from} -> {to}")?;
+                writeln!(f, "      msg:  {preview}")
+            }
+            Self::UpdateAdmin {
+                contract,
+                label,
+                address,
+                from,
+                preview,
+                ..
+            } => {
+                writeln!(f, "  ~ update admin {contract} \"{label}\" at {address}")?;
+                writeln!(
+                    f,
+                    "      admin: {} -> {}",
+                    admin_display(from),
+                    admin_display(preview)
+                )
+            }
+            Self::Drift {
+                contract,
+                label,
+                message,
+            } => writeln!(f, "  ! {contract} \"{label}\": {message}"),
+            Self::Warning { contract, message } => writeln!(f, "  ! {contract}: {message}"),
+        }
+    }
+}
+
+/// The changes needed to apply a manifest, as shown by `aq deploy --plan`.
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    pub actions: Vec<Action>,
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.actions.is_empty() {
+            return writeln!(f, "No changes. The deployment is up to date.");
+        }
+        for action in &self.actions {
+            writeln!(f, "{action}")?;
+        }
+        let count = |pred: fn(&Action) -> bool| self.actions.iter().filter(|a| pred(a)).count();
+        writeln!(
+            f,
+            "Plan: {} to store, {} to instantiate, {} to migrate, {} admin changes, {} warnings.",
+            count(|a| matches!(a, Action::Store { .. })),
+            count(|a| matches!(a, Action::Instantiate { .. })),
+            count(|a| matches!(a, Action::Migrate { .. })),
+            count(|a| matches!(a, Action::UpdateAdmin { .. })),
+            count(|a| matches!(a, Action::Drift { .. } | Action::Warning { .. })),
+        )
+    }
+}
+
+impl Plan {
+    /// Warn about stored code that anyone could instantiate, for plans against a mainnet.
+    pub fn check_access(&mut self) {
+        let actions = std::mem::take(&mut self.actions);
+        for action in actions {
+            let warning = match &action {
+                Action::Store {
+                    contract, access, ..
+                } => open_access_warning(access.as_ref()).map(|message| Action::Warning {
+                    contract: contract.clone(),
+                    message: message.to_string(),
+                }),
+                _ => None,
+            };
+            self.actions.push(action);
+            self.actions.extend(warning);
+        }
+    }
+}
+
+/// Compare a manifest with `contracts.json` and the chain, without sending transactions or
+/// changing `contracts.json`.
+pub async fn plan(env: &Env, manifest: &Manifest) -> Result<Plan> {
+    let mut pending = Pending::default();
+    let mut plan = Plan::default();
+    for contract in &manifest.contracts {
+        plan.actions
+            .extend(plan_contract(env, contract, &mut pending).await?);
+    }
+    Ok(plan)
+}
+
+/// Plan the changes for one contract. Refs of contracts in `pending` are treated as unknown.
+pub async fn plan_contract(
+    env: &Env,
+    contract: &ManifestContract,
+    pending: &mut Pending,
+) -> Result<Vec<Action>> {
+    let mut actions = vec![];
+
+    let bytecode = std::fs::read(&contract.wasm)
+        .map_err(|e| anyhow::anyhow!("Could not read wasm {}: {e}", contract.wasm.display()))?;
+    let checksum = checksum(&bytecode);
+    let account = env.executor.address();
+    let code = match stored_code_id(env, &contract.name, &checksum).await? {
+        Some(code_id) => CodeRef::Existing(code_id),
+        None => {
+            let interpolator = Interpolator {
+                refs: &env.refs,
+                account: &account,
+                pending: Some(pending),
+            };
+            let preview = contract
+                .instantiate_permission
+                .as_ref()
+                .map(|access| interpolator.access(access))
+                .transpose()?;
+            actions.push(Action::Store {
+                contract: contract.name.clone(),
+                wasm: contract.wasm.clone(),
+                checksum,
+                access: contract.instantiate_permission.clone(),
+                preview,
+            });
+            pending.code.insert(contract.name.clone());
+            CodeRef::New
+        }
+    };
+
+    for instance in &contract.instances {
+        let interpolator = Interpolator {
+            refs: &env.refs,
+            account: &account,
+            pending: Some(pending),
+        };
+        let admin = instance
+            .admin
+            .as_deref()
+            .map(|admin| interpolator.text(admin))
+            .transpose()?;
+
+        let Some(existing) = find_instance(env, &contract.name, &instance.label) else {
+            let preview = (
+                interpolator.value(&instance.msg)?,
+                admin,
+                interpolator.text(&instance.funds)?,
+            );
+            actions.push(Action::Instantiate {
+                contract: contract.name.clone(),
+                instance: instance.clone(),
+                code,
+                preview,
+            });
+            pending.instances.insert(contract.name.clone());
+            continue;
+        };
+        let address = existing.address.clone();
+        let drift = |message: String| Action::Drift {
+            contract: contract.name.clone(),
+            label: instance.label.clone(),
+            message,
+        };
+
+        let info = match env.querier.contract_info(address.clone()).await {
+            Ok(info) => info,
+            Err(e) if e.is::<ContractNotFound>() => {
+                actions.push(drift(format!(
+                    "{address} is in contracts.json but was not found on chain"
+                )));
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        if info.label != instance.label {
+            actions.push(drift(format!(
+                "the label on chain is \"{}\", labels can't be changed",
+                info.label
+            )));
+        }
+
+        if code != CodeRef::Existing(info.code_id) {
+            match &instance.migrate_msg {
+                Some(msg) => actions.push(Action::Migrate {
+                    contract: contract.name.clone(),
+                    label: instance.label.clone(),
+                    address: address.clone(),
+                    from: info.code_id,
+                    to: code,
+                    msg: msg.clone(),
+                    preview: interpolator.value(msg)?,
+                }),
+                None => actions.push(drift(format!(
+                    "runs code {} instead of {code}, set migrate_msg to migrate it",
+                    info.code_id
+                ))),
+            }
+        }
+
+        // A missing admin leaves the instance's admin alone, clearing it must be asked for.
+        let target = match admin {
+            Some(admin) => Some(Some(admin)),
+            None if instance.clear_admin => Some(None),
+            None => None,
+        };
+        if let Some(target) = target.filter(|target| *target != info.admin) {
+            actions.push(Action::UpdateAdmin {
+                contract: contract.name.clone(),
+                label: instance.label.clone(),
+                address,
+                from: info.admin,
+                to: instance.admin.clone(),
+                preview: target,
+            });
+        }
+    }
+
+    Ok(actions)
+}
@@ -0,0 +1,356 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::{
+    account::AccountWithInfo, config::devnet::DevnetConfig, project::Project, ContractRefs, Network,
+};
+
+const VALIDATOR_KEY: &str = "validator";
+const PID_FILE: &str = "aquarium.pid";
+const LOG_FILE: &str = "aquarium.log";
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A local chain for the project, run from the binary in the `[devnet]` table of
+/// `Aquarium.toml`. Processes are managed with `kill`, so this needs a Unix host.
+pub struct Devnet<'a> {
+    project: &'a Project,
+    pub config: DevnetConfig,
+    pub network: Network,
+    pub home: PathBuf,
+}
+
+impl<'a> Devnet<'a> {
+    pub fn new(project: &'a Project) -> Result<Self> {
+        let config = project.config.devnet.clone().unwrap_or_default();
+        let network = project
+            .config
+            .networks
+            .get(&config.network)
+            .cloned()
+            .ok_or(anyhow::anyhow!(
+                "Could not find network \"{}\" for the devnet",
+                config.network
+            ))?;
+        let home = project.root.join(&config.home);
+        Ok(Self {
+            project,
+            config,
+            network,
+            home,
+        })
+    }
+
+    /// Start the chain, creating its genesis on the first run, and wait for the LCD.
+    pub async fn up(&self) -> Result<()> {
+        if self.is_healthy().await {
+            println!("Devnet is already running at {}", self.network.lcd_addr);
+            return Ok(());
+        }
+        if self.pid()?.is_none() {
+            if !self.home.join("config").join("genesis.json").exists() {
+                if let Err(e) = self.init() {
+                    // Leave no half-written genesis behind for the next run to trip over.
+                    let _ = std::fs::remove_dir_all(&self.home);
+                    return Err(e);
+                }
+            }
+            self.start()?;
+        }
+        self.wait_until_healthy().await
+    }
+
+    /// Stop the chain, keeping its state.
+    pub fn down(&self) -> Result<()> {
+        let Some(pid) = self.pid()? else {
+            println!("Devnet is not running");
+            return Ok(());
+        };
+        Command::new("kill").arg(pid.to_string()).status()?;
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        while is_alive(pid) {
+            if Instant::now() > deadline {
+                return Err(anyhow::anyhow!("Devnet (pid {pid}) did not stop"));
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        std::fs::remove_file(self.home.join(PID_FILE))?;
+        println!("Stopped devnet (pid {pid})");
+        Ok(())
+    }
+
+    /// Stop the chain, delete its state and forget the contracts deployed to it.
+    pub fn reset(&self) -> Result<()> {
+        self.down()?;
+        if self.home.exists() {
+            std::fs::remove_dir_all(&self.home)?;
+            println!("Removed {}", self.home.display());
+        }
+        let refs_path = self.project.root.join("contracts.json");
+        let mut refs = ContractRefs::load_or_default(refs_path.clone())?;
+        if refs.networks.remove(&self.config.network).is_some() {
+            refs.save(refs_path)?;
+            println!("Removed {} refs from contracts.json", self.config.network);
+        }
+        Ok(())
+    }
+
+    pub async fn is_healthy(&self) -> bool {
+        self.network
+            .current_block_height()
+            .await
+            .is_ok_and(|height| height > 0)
+    }
+
+    /// The process ID of the running chain, if any.
+    pub fn pid(&self) -> Result<Option<u32>> {
+        let pid_file = self.home.join(PID_FILE);
+        let Ok(pid) = std::fs::read_to_string(&pid_file) else {
+            return Ok(None);
+        };
+        let pid = pid.trim().parse::<u32>()?;
+        if is_alive(pid) {
+            Ok(Some(pid))
+        } else {
+            std::fs::remove_file(pid_file)?;
+            Ok(None)
+        }
+    }
+
+    fn init(&self) -> Result<()> {
+        println!(
+            "Initializing {} devnet in {}",
+            self.config.binary,
+            self.home.display()
+        );
+        let denom = &self.network.gas_denom;
+        self.run(&["init", "aquarium", "--chain-id", &self.network.chain_id])?;
+        self.set_genesis_denom()?;
+        self.run(&["keys", "add", VALIDATOR_KEY, "--keyring-backend", "test"])?;
+
+        // Newer SDKs moved the genesis commands under a `genesis` subcommand.
+        let genesis: &[&str] = if self.run(&["genesis", "--help"]).is_ok() {
+            &["genesis"]
+        } else {
+            &[]
+        };
+        let validator_balance = format!("{}{denom}", self.config.balance.max(self.config.stake));
+        self.run(
+            &[
+                genesis,
+                &[
+                    "add-genesis-account",
+                    VALIDATOR_KEY,
+                    &validator_balance,
+                    "--keyring-backend",
+                    "test",
+                ],
+            ]
+            .concat(),
+        )?;
+
+        let balance = format!("{}{denom}", self.config.balance);
+        let mut accounts = self.project.config.accounts.iter().collect::<Vec<_>>();
+        accounts.sort_by_key(|(name, _)| *name);
+        for (name, account) in accounts {
+            let account = match AccountWithInfo::new(
+                account.clone(),
+                &self.project.config.hd_path,
+                &self.project.root,
+                &self.network.account_prefix,
+            ) {
+                Ok(account) => account,
+                Err(e) => {
+                    println!("Skipping account {name}: {e}");
+                    continue;
+                }
+            };
+            let address = account.address.to_string();
+            self.run(&[genesis, &["add-genesis-account", &address, &balance]].concat())?;
+            println!("Funded {name} ({address}) with {balance}");
+        }
+
+        let stake = format!("{}{denom}", self.config.stake);
+        self.run(
+            &[
+                genesis,
+                &[
+                    "gentx",
+                    VALIDATOR_KEY,
+                    &stake,
+                    "--chain-id",
+                    &self.network.chain_id,
+                    "--keyring-backend",
+                    "test",
+                ],
+            ]
+            .concat(),
+        )?;
+        self.run(&[genesis, &["collect-gentxs"]].concat())?;
+        self.configure()
+    }
+
+    /// Use the network's gas denom wherever the generated genesis uses `stake`.
+    fn set_genesis_denom(&self) -> Result<()> {
+        fn replace(value: &mut Value, denom: &str) {
+            match value {
+                Value::String(s) if s == "stake" => *s = denom.to_string(),
+                Value::Array(items) => items.iter_mut().for_each(|item| replace(item, denom)),
+                Value::Object(map) => map.values_mut().for_each(|item| replace(item, denom)),
+                _ => {}
+            }
+        }
+        let path = self.home.join("config").join("genesis.json");
+        let mut genesis: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        replace(&mut genesis, &self.network.gas_denom);
+        std::fs::write(path, serde_json::to_string_pretty(&genesis)?)?;
+        Ok(())
+    }
+
+    /// Serve the LCD on the network's port, accept zero fees and produce blocks every second.
+    fn configure(&self) -> Result<()> {
+        let port = self
+            .network
+            .lcd_addr
+            .trim_end_matches('/')
+            .rsplit(':')
+            .next()
+            .and_then(|port| port.parse::<u16>().ok())
+            .unwrap_or(1317);
+        edit_toml(&self.home.join("config").join("app.toml"), |app| {
+            app.insert(
+                "minimum-gas-prices".to_string(),
+                format!("0{}", self.network.gas_denom).into(),
+            );
+            let api = table(app, "api");
+            api.insert("enable".to_string(), true.into());
+            api.insert(
+                "address".to_string(),
+                format!("tcp://0.0.0.0:{port}").into(),
+            );
+            api.insert("enabled-unsafe-cors".to_string(), true.into());
+        })?;
+        edit_toml(&self.home.join("config").join("config.toml"), |config| {
+            table(config, "consensus").insert("timeout_commit".to_string(), "1s".into());
+        })
+    }
+
+    fn start(&self) -> Result<()> {
+        let log_path = self.home.join(LOG_FILE);
+        let log = File::create(&log_path)?;
+        let child = self
+            .command()
+            .arg("start")
+            .arg("--home")
+            .arg(&self.home)
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .spawn()
+            .map_err(|e| self.spawn_error(e))?;
+        std::fs::write(self.home.join(PID_FILE), child.id().to_string())?;
+        println!(
+            "Started {} (pid {}), logging to {}",
+            self.config.binary,
+            child.id(),
+            log_path.display()
+        );
+        Ok(())
+    }
+
+    async fn wait_until_healthy(&self) -> Result<()> {
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            if self.is_healthy().await {
+                println!("Devnet is up at {}", self.network.lcd_addr);
+                return Ok(());
+            }
+            if self.pid()?.is_none() {
+                return Err(anyhow::anyhow!(
+                    "Devnet exited, see {}",
+                    self.home.join(LOG_FILE).display()
+                ));
+            }
+            if Instant::now() > deadline {
+                return Err(anyhow::anyhow!(
+                    "Timed out waiting for the LCD at {}",
+                    self.network.lcd_addr
+                ));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    fn command(&self) -> Command {
+        Command::new(&self.config.binary)
+    }
+
+    fn spawn_error(&self, e: std::io::Error) -> anyhow::Error {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            anyhow::anyhow!(
+                "Could not find `{}` on PATH, set `binary` in the [devnet] table of Aquarium.toml",
+                self.config.binary
+            )
+        } else {
+            anyhow::anyhow!(e)
+        }
+    }
+
+    /// Run a chain binary command against the devnet home, failing with its stderr.
+    fn run(&self, args: &[&str]) -> Result<()> {
+        let output = self
+            .command()
+            .args(args)
+            .arg("--home")
+            .arg(&self.home)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| self.spawn_error(e))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "`{} {}` failed: {}",
+                self.config.binary,
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn is_alive(pid: u32) -> bool {
+    Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn edit_toml(path: &Path, edit: impl FnOnce(&mut toml::Table)) -> Result<()> {
+    let mut contents: toml::Table =
+        toml::from_str(&std::fs::read_to_string(path)?).map_err(|e| anyhow::anyhow!(e))?;
+    edit(&mut contents);
+    std::fs::write(
+        path,
+        toml::to_string(&contents).map_err(|e| anyhow::anyhow!(e))?,
+    )?;
+    Ok(())
+}
+
+fn table<'a>(parent: &'a mut toml::Table, key: &str) -> &'a mut toml::Table {
+    let value = parent
+        .entry(key.to_string())
+        .or_insert_with(|| toml::Table::new().into());
+    if !value.is_table() {
+        *value = toml::Table::new().into();
+    }
+    value.as_table_mut().unwrap()
+}
@@ -1,12 +1,21 @@
 pub mod project;
 pub(crate) mod cli;
+pub mod admin;
+pub mod authz;
 pub mod client;
+pub mod codegen;
 pub mod config;
+pub mod deploy;
+pub mod devnet;
+pub mod mock;
+pub mod task;
 
-pub use aquarium_macro::task;
+pub use aquarium_macro::{task, ContractClient};
+pub use task::run_tasks;
 pub mod internal {
-    pub use {anyhow::Result as AnyhowResult, tokio};
+    pub use {anyhow::Result as AnyhowResult, async_trait, clap, cosmwasm_std, inventory, serde, serde_json, tokio};
     pub use crate::project::Project;
+    pub use crate::task::{parse_task_args, EnvGuard, TaskDefinition, TaskFuture, TaskOutput};
     pub use crate::cli::*;
 }
 
@@ -15,8 +24,11 @@ pub mod utils {
 }
 
 pub use {
+    client::contract::*,
+    client::dynamic::*,
     client::query::*,
     client::signing::*,
+    client::transport::*,
     config::env::*,
     config::network::*,
     config::refs::*,
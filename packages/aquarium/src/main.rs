@@ -1,11 +1,29 @@
-use std::process::Command;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::Result;
 
 use aquarium::internal::Project;
 
-use aquarium::internal::args::{Commands, RunTaskArgs, TaskCommands};
+use aquarium::client::messages;
+use aquarium::client::offline::{OfflineTx, TxSigner};
+use aquarium::deploy::{Manifest, LABEL_ATTR};
+use aquarium::devnet::Devnet;
+use aquarium::internal::args::{
+    AccountArgs, AccountCommands, AdminCommands, AuthzArgs, AuthzCommands, CodegenArgs,
+    CodegenCommands, Commands, ContractArgs, ContractCommands, DeployArgs, DevnetArgs,
+    DevnetCommands, ExportArgs, ExportCommands, RunTaskArgs, TaskCommands, TxArgs, TxCommands,
+};
+use aquarium::internal::task::TaskArgs;
 use aquarium::internal::Cli;
+use aquarium::internal::EnvGuard;
+use aquarium::task::{TaskInfo, LIST_TASKS_FLAG, REGISTRY_MARKER, RESUME_FLAG};
+use aquarium::utils::{ensure_tx_success, parse_coins};
+use aquarium::{ContractRefs, Executor, FeeOptions, Querier};
 use clap::Parser;
 
 #[tokio::main]
@@ -17,66 +35,677 @@ async fn main() -> Result<()> {
             println!("Project initialized at {}!", project.root.display());
         }
         Commands::Task(args) => run_task(args).await?,
-        Commands::Contract(_) => todo!(),
+        Commands::Contract(args) => contract(args).await?,
+        Commands::Deploy(args) => deploy(args).await?,
+        Commands::Codegen(args) => codegen(args)?,
+        Commands::Export(args) => export(args)?,
+        Commands::Devnet(args) => devnet(args).await?,
+        Commands::Account(args) => account(args).await?,
+        Commands::Authz(args) => authz(args).await?,
+        Commands::Tx(args) => tx(args).await?,
     }
     Ok(())
 }
 
+async fn deploy(args: DeployArgs) -> Result<()> {
+    let project = Project::load()?;
+    let manifest = Manifest::load(&args.manifest)?;
+    if args.plan {
+        // Planning is read-only, so contracts.json isn't saved afterwards.
+        let env = project.env_from_args(&args.task)?;
+        let mut plan = aquarium::deploy::plan(&env, &manifest).await?;
+        if project.config.networks[&env.network].mainnet {
+            plan.check_access();
+        }
+        print!("{plan}");
+        return Ok(());
+    }
+    let mut env = EnvGuard::new(project.env_from_args(&args.task)?);
+    let result = aquarium::deploy::apply(&mut env, &manifest).await;
+    env.finish()?;
+    result
+}
+
+fn codegen(args: CodegenArgs) -> Result<()> {
+    match args.command {
+        CodegenCommands::Rust { schema_dirs, out } => {
+            let out = match out {
+                Some(out) => out,
+                None => {
+                    let project = Project::load()?;
+                    project
+                        .root
+                        .join(project.config.scripts_path)
+                        .join("src")
+                        .join("contracts")
+                }
+            };
+            let schemas = aquarium::codegen::load_schemas(&schema_dirs)?;
+            for path in aquarium::codegen::write_rust(&schemas, &out)? {
+                println!("Wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn export(args: ExportArgs) -> Result<()> {
+    match args.command {
+        ExportCommands::Ts {
+            out,
+            network,
+            schema_dirs,
+        } => {
+            let project = Project::load()?;
+            let out = out.unwrap_or(project.root.join("ts"));
+            let mut written = aquarium::codegen::write_ts_refs(&project, &network, &out)?;
+            if !schema_dirs.is_empty() {
+                let schemas = aquarium::codegen::load_schemas(&schema_dirs)?;
+                written.extend(aquarium::codegen::write_ts_types(&schemas, &out)?);
+            }
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn devnet(args: DevnetArgs) -> Result<()> {
+    let project = Project::load()?;
+    let devnet = Devnet::new(&project)?;
+    match args.command {
+        DevnetCommands::Up {} => devnet.up().await,
+        DevnetCommands::Down {} => devnet.down(),
+        DevnetCommands::Reset {} => devnet.reset(),
+    }
+}
+
+async fn account(args: AccountArgs) -> Result<()> {
+    match args.command {
+        AccountCommands::Fund {
+            name,
+            amount,
+            from,
+            network,
+        } => {
+            let project = Project::load()?;
+            let (network_name, network) = project.network_from_args(&TaskArgs {
+                account: None,
+                network,
+            })?;
+            let mut env = project.env_on(network_name, &network, None)?;
+            if let Some(from) = from {
+                env = env.with_faucet(Box::new(project.signing_client(&network, &from)?));
+            }
+            let address = project.address(&network, &name)?;
+            let hash = env.fund(address.clone(), parse_coins(&amount)?).await?;
+            if name == address {
+                println!("Sent {amount} to {address} in {hash}");
+            } else {
+                println!("Sent {amount} to {name} ({address}) in {hash}");
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn authz(args: AuthzArgs) -> Result<()> {
+    let project = Project::load()?;
+    let (network_name, network) = project.network_from_args(&args.task)?;
+    let env = project.env_on(network_name, &network, args.task.account.clone())?;
+    let network = &network;
+    let account = env.executor.address();
+    let hash = match args.command {
+        AuthzCommands::Grant { grantee, msg, days } => {
+            let grantee = project.address(network, &grantee)?;
+            let expiration = match days {
+                Some(days) => Some(
+                    SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + days * 24 * 60 * 60,
+                ),
+                None => None,
+            };
+            env.executor.grant(grantee, msg, expiration, None).await?
+        }
+        AuthzCommands::Revoke { grantee, msg } => {
+            let grantee = project.address(network, &grantee)?;
+            env.executor.revoke(grantee, msg, None).await?
+        }
+        AuthzCommands::List {} => {
+            let expiry = |expiration: Option<&str>| match expiration {
+                Some(expiration) => format!("until {expiration}"),
+                None => "no expiration".to_string(),
+            };
+            for grant in aquarium::authz::grants_by(network, &account).await? {
+                println!(
+                    "{account} -> {}: {}, {}",
+                    grant.grantee,
+                    grant.authorization,
+                    expiry(grant.expiration.as_deref())
+                );
+            }
+            for grant in aquarium::authz::grants_to(network, &account).await? {
+                println!(
+                    "{} -> {account}: {}, {}",
+                    grant.granter,
+                    grant.authorization,
+                    expiry(grant.expiration.as_deref())
+                );
+            }
+            return Ok(());
+        }
+    };
+    println!("Waiting for authz hash {hash}");
+    ensure_tx_success(&env.executor.wait_for_transaction(hash).await?)?;
+    Ok(())
+}
+
+async fn tx(args: TxArgs) -> Result<()> {
+    let project = Project::load()?;
+    let tx_args = match &args.command {
+        TxCommands::Execute { tx, .. }
+        | TxCommands::Migrate { tx, .. }
+        | TxCommands::UpdateAdmin { tx, .. }
+        | TxCommands::ClearAdmin { tx, .. } => tx.clone(),
+        TxCommands::Sign { file, account, out } => {
+            let name = account
+                .clone()
+                .or(project.config.accounts.keys().next().cloned())
+                .ok_or(anyhow::anyhow!("No accounts specified"))?;
+            let signer = project.signer(&name)?;
+            let mut tx = OfflineTx::load(file)?;
+            tx.sign(signer.as_ref()).await?;
+            tx.save(out.as_ref().unwrap_or(file))?;
+            print_missing_signatures(&tx);
+            return Ok(());
+        }
+        TxCommands::Multisign { files, out } => {
+            let mut tx = OfflineTx::load(&files[0])?;
+            for file in &files[1..] {
+                tx.merge(OfflineTx::load(file)?)?;
+            }
+            tx.save(out)?;
+            print_missing_signatures(&tx);
+            return Ok(());
+        }
+        TxCommands::Broadcast { file, network } => {
+            let (_, network) = project.network_from_args(&TaskArgs {
+                account: None,
+                network: network.clone(),
+            })?;
+            let tx = OfflineTx::load(file)?;
+            if tx.chain_id != network.chain_id {
+                return Err(anyhow::anyhow!(
+                    "The transaction is for chain {}, not {}",
+                    tx.chain_id,
+                    network.chain_id
+                ));
+            }
+            let hash = tx.broadcast(&network).await?;
+            println!("Waiting for transaction hash {hash}");
+            let client = aquarium::QueryClient::new(network);
+            ensure_tx_success(&client.wait_for_transaction(hash).await?)?;
+            return Ok(());
+        }
+    };
+
+    let (network_name, network) = project.network_from_args(&tx_args.task)?;
+    let env = project.env_on(network_name, &network, tx_args.task.account.clone())?;
+    let network = &network;
+    let from = tx_args
+        .from
+        .clone()
+        .or(tx_args.task.account.clone())
+        .or(project.config.accounts.keys().next().cloned())
+        .ok_or(anyhow::anyhow!("No accounts specified"))?;
+    let sender = project.address(network, &from)?;
+    let json = |msg: &str| -> Result<Vec<u8>> {
+        let msg: serde_json::Value = serde_json::from_str(msg)?;
+        Ok(serde_json::to_vec(&msg)?)
+    };
+    let msg = match args.command {
+        TxCommands::Execute {
+            contract,
+            msg,
+            funds,
+            ..
+        } => messages::execute(
+            &sender,
+            &aquarium::admin::resolve_contract(&env, &contract)?,
+            json(&msg)?,
+            parse_coins(&funds)?,
+        )?,
+        TxCommands::Migrate {
+            contract,
+            code_id,
+            msg,
+            ..
+        } => messages::migrate(
+            &sender,
+            &aquarium::admin::resolve_contract(&env, &contract)?,
+            code_id,
+            json(&msg)?,
+        )?,
+        TxCommands::UpdateAdmin {
+            contract, admin, ..
+        } => messages::update_admin(
+            &sender,
+            &aquarium::admin::resolve_contract(&env, &contract)?,
+            &project.address(network, &admin)?,
+        )?,
+        TxCommands::ClearAdmin { contract, .. } => messages::clear_admin(
+            &sender,
+            &aquarium::admin::resolve_contract(&env, &contract)?,
+        )?,
+        _ => unreachable!(),
+    };
+
+    let multisig = project.multisig(&from)?;
+    if !tx_args.generate_only {
+        if multisig.is_some() {
+            return Err(anyhow::anyhow!(
+                "{from} is a multisig, pass --generate-only and sign with its members"
+            ));
+        }
+        let mut client = project.signing_client(network, &from)?;
+        if let Some(sign_mode) = tx_args.sign_mode {
+            client = client.with_sign_mode(sign_mode);
+        }
+        let hash = client.execute_any(vec![msg], tx_args.memo).await?;
+        println!("Waiting for transaction hash {hash}");
+        ensure_tx_success(&env.executor.wait_for_transaction(hash).await?)?;
+        return Ok(());
+    }
+
+    let mut sign_mode = tx_args.sign_mode;
+    let signer = match multisig {
+        Some(multisig) => {
+            let signers = tx_args
+                .signers
+                .iter()
+                .map(|signer| project.public_key(signer))
+                .collect::<Result<Vec<_>>>()?;
+            if let Some(signer) = signers
+                .iter()
+                .find(|signer| !multisig.public_keys.contains(signer))
+            {
+                return Err(anyhow::anyhow!(
+                    "{} is not a member of {from}",
+                    cosmwasm_std::Binary(signer.to_bytes()).to_base64()
+                ));
+            }
+            if (signers.len() as u32) < multisig.threshold {
+                return Err(anyhow::anyhow!(
+                    "{from} needs {} signers, pass them with --signers",
+                    multisig.threshold
+                ));
+            }
+            TxSigner::Multisig { multisig, signers }
+        }
+        None => match project.config.accounts.get(&from) {
+            Some(account) => {
+                sign_mode = sign_mode.or(Some(account.sign_mode()));
+                TxSigner::Single(project.public_key(&from)?)
+            }
+            None => {
+                return Err(anyhow::anyhow!(
+                    "{from} is not an account or multisig in Aquarium.toml"
+                ))
+            }
+        },
+    };
+    let (account_number, sequence) = network.account_sequence_numbers(sender.clone()).await?;
+    let fee = FeeOptions::default()
+        .with_gas_limit(tx_args.gas)
+        .to_fee(network, tx_args.gas)
+        .await?;
+    let tx = OfflineTx::new(
+        &network.chain_id,
+        &sender,
+        account_number,
+        sequence,
+        &signer,
+        vec![msg],
+        tx_args.memo,
+        0,
+        fee,
+        sign_mode.unwrap_or_default(),
+    )?;
+    match tx_args.out {
+        Some(out) => {
+            tx.save(&out)?;
+            println!("Wrote unsigned transaction to {}", out.display());
+        }
+        None => println!("{}", serde_json::to_string_pretty(&tx)?),
+    }
+    Ok(())
+}
+
+fn print_missing_signatures(tx: &OfflineTx) {
+    let missing = tx.missing_signatures();
+    if missing.is_empty() {
+        println!("Every signer has signed, ready to broadcast");
+    } else {
+        println!("Waiting for signatures from {}", missing.join(", "));
+    }
+}
+
+async fn contract(args: ContractArgs) -> Result<()> {
+    match args.command {
+        ContractCommands::List { network } => {
+            let project = Project::load()?;
+            let refs = ContractRefs::load_or_default(project.root.join("contracts.json"))?;
+            let mut networks = refs.networks.iter().collect::<Vec<_>>();
+            networks.sort_by_key(|(name, _)| *name);
+            if let Some(network) = &network {
+                if !refs.networks.contains_key(network) {
+                    return Err(anyhow::anyhow!(
+                        "No contracts deployed to network {network} in contracts.json"
+                    ));
+                }
+                networks.retain(|(name, _)| *name == network);
+            }
+            for (name, refs) in networks {
+                println!("{name}");
+                let mut contracts = refs.contracts.iter().collect::<Vec<_>>();
+                contracts.sort_by_key(|(name, _)| *name);
+                for (contract, refs) in contracts {
+                    let code_ids = refs
+                        .code_ids
+                        .iter()
+                        .map(|code_id| code_id.to_string())
+                        .collect::<Vec<_>>();
+                    println!("  {contract}: code {}", code_ids.join(", "));
+                    for instance in &refs.instances {
+                        let label = instance
+                            .attrs
+                            .get(LABEL_ATTR)
+                            .and_then(|label| label.as_str())
+                            .unwrap_or("(no label)");
+                        println!(
+                            "    {label} at {} (code {})",
+                            instance.address, instance.code_id
+                        );
+                    }
+                }
+            }
+        }
+        ContractCommands::Admin {
+            contract,
+            command,
+            task,
+        } => {
+            let project = Project::load()?;
+            let env = project.env_from_args(&task)?;
+            let address = aquarium::admin::resolve_contract(&env, &contract)?;
+            let hash = match command {
+                AdminCommands::Show {} => {
+                    let info = env.querier.contract_info(address.clone()).await?;
+                    println!("{}", info.admin.as_deref().unwrap_or("(none)"));
+                    return Ok(());
+                }
+                AdminCommands::Set { admin } => {
+                    let admin = project.address(&project.config.networks[&env.network], &admin)?;
+                    env.executor
+                        .update_admin(address.clone(), admin, None)
+                        .await?
+                }
+                AdminCommands::Clear {} => env.executor.clear_admin(address.clone(), None).await?,
+            };
+            println!("Waiting for admin update hash {hash}");
+            ensure_tx_success(&env.executor.wait_for_transaction(hash).await?)?;
+            let info = env.querier.contract_info(address.clone()).await?;
+            println!(
+                "Admin of {address} is now {}",
+                info.admin.as_deref().unwrap_or("(none)")
+            );
+        }
+        ContractCommands::Audit { admin, task } => {
+            let project = Project::load()?;
+            let env = project.env_from_args(&task)?;
+            let network = &project.config.networks[&env.network];
+            let admin = admin.or(network.admin.clone()).ok_or(anyhow::anyhow!(
+                "No expected admin, pass --admin or set admin for network \"{}\" in Aquarium.toml",
+                env.network
+            ))?;
+            let admin = project.address(network, &admin)?;
+            let entries = aquarium::admin::audit(&env, &admin).await?;
+            for entry in &entries {
+                let label = entry
+                    .label
+                    .as_ref()
+                    .map(|label| format!(" ({label})"))
+                    .unwrap_or_default();
+                if entry.issues.is_empty() {
+                    println!("= {}{label} at {}: ok", entry.contract, entry.address);
+                }
+                for issue in &entry.issues {
+                    println!("! {}{label} at {}: {issue}", entry.contract, entry.address);
+                }
+            }
+            let flagged = entries.iter().filter(|e| !e.issues.is_empty()).count();
+            println!("Audited {} instances, {flagged} flagged.", entries.len());
+            if flagged > 0 {
+                return Err(anyhow::anyhow!("{flagged} instances need attention"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The binary targets of the scripts crate.
+struct ScriptsTargets {
+    package: String,
+    bins: Vec<String>,
+    /// The built executable of each binary.
+    executables: HashMap<String, PathBuf>,
+    /// Tasks registered in the registry binary, read once per invocation.
+    tasks: Vec<TaskInfo>,
+}
+
+impl ScriptsTargets {
+    /// Build the scripts crate once and read the tasks of its registry binary.
+    fn load(project: &Project) -> Result<Self> {
+        let dir = project.root.join(project.config.scripts_path.clone());
+        let manifest = Command::new("cargo")
+            .arg("read-manifest")
+            .current_dir(&dir)
+            .output()?;
+        let manifest = serde_json::from_slice::<serde_json::Value>(&manifest.stdout)?;
+        let package = manifest["name"].as_str().unwrap_or_default().to_string();
+        let bins = manifest["targets"]
+            .as_array()
+            .map(|targets| {
+                targets
+                    .iter()
+                    .filter(|t| {
+                        t["kind"]
+                            .as_array()
+                            .is_some_and(|k| k.contains(&"bin".into()))
+                    })
+                    .filter_map(|t| t["name"].as_str().map(|s| s.to_string()))
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+
+        let build = Command::new("cargo")
+            .arg("build")
+            .arg("--quiet")
+            .arg("--bins")
+            .arg("--message-format=json-render-diagnostics")
+            .current_dir(&dir)
+            .stderr(Stdio::inherit())
+            .output()?;
+        if !build.status.success() {
+            return Err(anyhow::anyhow!("Could not build the scripts crate"));
+        }
+        let executables = build
+            .stdout
+            .split(|byte| *byte == b'\n')
+            .filter_map(|line| serde_json::from_slice::<serde_json::Value>(line).ok())
+            .filter(|message| message["reason"] == "compiler-artifact")
+            .filter_map(|message| {
+                let name = message["target"]["name"].as_str()?.to_string();
+                let executable = message["executable"].as_str()?;
+                Some((name, PathBuf::from(executable)))
+            })
+            .collect();
+
+        let mut targets = Self {
+            package,
+            bins,
+            executables,
+            tasks: vec![],
+        };
+        targets.tasks = targets.read_tasks(&dir)?;
+        Ok(targets)
+    }
+
+    /// The binary holding tasks registered with `#[aquarium::task(name = "...")]`, if any.
+    fn registry_bin(&self) -> Option<&str> {
+        self.bins
+            .iter()
+            .find(|bin| **bin == self.package)
+            .map(|bin| bin.as_str())
+    }
+
+    /// Ask the registry binary for its tasks, if its main is `aquarium::run_tasks`. Other
+    /// binaries would run their task instead of listing, so they are never started.
+    fn read_tasks(&self, dir: &Path) -> Result<Vec<TaskInfo>> {
+        let Some(executable) = self
+            .registry_bin()
+            .and_then(|bin| self.executables.get(bin))
+        else {
+            return Ok(vec![]);
+        };
+        let contents = std::fs::read(executable)?;
+        if !contents
+            .windows(REGISTRY_MARKER.len())
+            .any(|window| window == REGISTRY_MARKER.as_bytes())
+        {
+            return Ok(vec![]);
+        }
+        let output = Command::new(executable)
+            .arg(format!("--{LIST_TASKS_FLAG}"))
+            .current_dir(dir)
+            .stderr(Stdio::inherit())
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Could not list the tasks of {}",
+                executable.display()
+            ));
+        }
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+
+    /// A command running the built binary `bin` from the scripts crate.
+    fn command(&self, project: &Project, bin: &str) -> Result<Command> {
+        let executable = self
+            .executables
+            .get(bin)
+            .ok_or(anyhow::anyhow!("Binary \"{bin}\" was not built"))?;
+        let mut command = Command::new(executable);
+        command.current_dir(project.root.join(project.config.scripts_path.clone()));
+        Ok(command)
+    }
+}
+
 async fn run_task(args: RunTaskArgs) -> Result<()> {
     match args.command {
         TaskCommands::List {} => {
             let project = Project::load()?;
-            let manifest = Command::new("cargo")
-                .arg("read-manifest")
-                .current_dir(&project.root.join(project.config.scripts_path.clone()))
-                .output()?;
-            let manifest = serde_json::from_slice::<serde_json::Value>(&manifest.stdout)?;
-            let targets = manifest["targets"]
-                .as_array()
-                .map(|targets| {
-                    targets
-                        .iter()
-                        .filter_map(|t| t["name"].as_str().map(|s| s.to_string()))
-                        .collect::<Vec<String>>()
-                })
-                .unwrap_or_default();
+            let targets = ScriptsTargets::load(&project)?;
+            let tasks = &targets.tasks;
             println!("Available tasks:");
-            for target in targets {
+            for task in tasks {
+                let deps = if task.depends_on.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (depends on: {})", task.depends_on.join(", "))
+                };
+                if task.about.is_empty() {
+                    println!("  - \"{}\"{}", task.name, deps);
+                } else {
+                    println!("  - \"{}\": {}{}", task.name, task.about, deps);
+                }
+            }
+            for (name, pipeline) in &project.config.pipelines {
+                let steps = pipeline
+                    .steps
+                    .iter()
+                    .map(|step| step.task())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                if pipeline.about.is_empty() {
+                    println!("  - \"{}\" (pipeline: {})", name, steps);
+                } else {
+                    println!("  - \"{}\": {} (pipeline: {})", name, pipeline.about, steps);
+                }
+            }
+            let registry_bin = if tasks.is_empty() {
+                None
+            } else {
+                targets.registry_bin()
+            };
+            for target in targets
+                .bins
+                .iter()
+                .filter(|bin| Some(bin.as_str()) != registry_bin)
+            {
                 println!("  - \"{}\"", target);
             }
             Ok(())
         }
-        TaskCommands::Run { name, mut trailing } => {
+        TaskCommands::Run { name, trailing } => {
             let project = Project::load()?;
-            let manifest = Command::new("cargo")
-                .arg("read-manifest")
-                .current_dir(&project.root.join(project.config.scripts_path.clone()))
-                .output()?;
-            let manifest = serde_json::from_slice::<serde_json::Value>(&manifest.stdout)?;
-            let targets = manifest["targets"]
-                .as_array()
-                .map(|targets| {
-                    targets
-                        .iter()
-                        .filter_map(|t| t["name"].as_str().map(|s| s.to_string()))
-                        .collect::<Vec<String>>()
-                })
-                .unwrap_or_default();
-            if !targets.contains(&name) {
-                println!("Task \"{}\" not found", name);
-                return Ok(());
-            }
-            if !trailing.is_empty() {
-                trailing.insert(0, "--".to_string());
-            }
-            let status = Command::new("cargo")
-                .arg("run")
-                .arg("--quiet")
-                .arg("--bin")
-                .arg(name)
+            let targets = ScriptsTargets::load(&project)?;
+            let mut command = if targets.tasks.iter().any(|task| task.name == name)
+                || project.config.pipelines.contains_key(&name)
+            {
+                // Registered tasks and pipelines are subcommands of the registry binary.
+                let registry_bin = targets.registry_bin().ok_or(anyhow::anyhow!(
+                    "\"{name}\" runs registered tasks, but the scripts crate has no binary named \"{}\" calling aquarium::run_tasks",
+                    targets.package
+                ))?;
+                let mut command = targets.command(&project, registry_bin)?;
+                command.arg(&name);
+                command
+            } else if targets.bins.contains(&name) {
+                targets.command(&project, &name)?
+            } else {
+                return Err(anyhow::anyhow!("Task \"{}\" not found", name));
+            };
+            let status = command.args(trailing).status()?;
+            if !status.success() {
+                return Err(anyhow::anyhow!(
+                    "Task \"{}\" failed with status {}",
+                    name,
+                    status
+                ));
+            }
+            println!("Task completed with status {}", status);
+            Ok(())
+        }
+        TaskCommands::Resume { name, trailing } => {
+            let project = Project::load()?;
+            let targets = ScriptsTargets::load(&project)?;
+            let registry_bin = targets.registry_bin().ok_or(anyhow::anyhow!(
+                "Only registered tasks and pipelines can be resumed"
+            ))?;
+            let status = targets
+                .command(&project, registry_bin)?
+                .arg(format!("--{RESUME_FLAG}"))
+                .arg(&name)
                 .args(trailing)
-                .current_dir(&project.root.join(project.config.scripts_path.clone()))
                 .status()?;
+            if !status.success() {
+                return Err(anyhow::anyhow!(
+                    "Task \"{}\" failed with status {}",
+                    name,
+                    status
+                ));
+            }
             println!("Task completed with status {}", status);
             Ok(())
         }
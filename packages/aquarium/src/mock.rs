@@ -0,0 +1,647 @@
+use std::{
+    collections::HashMap,
+    sync::mpsc::{channel, Sender},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use cosmrs::{
+    bank::MsgSend,
+    cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract},
+    proto::{
+        cosmwasm::wasm::v1::{MsgClearAdmin, MsgUpdateAdmin},
+        traits::MessageExt,
+    },
+    tx::Msg,
+    Any,
+};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, Event, QueryRequest, WasmMsg,
+    WasmQuery,
+};
+use cw_multi_test::{next_block, App, AppResponse};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub use cw_multi_test::{Contract, ContractWrapper};
+
+use crate::{
+    client::messages::{self, AccessConfig},
+    deploy::checksum,
+    CodeInfo, ContractInfo, ContractNotFound, Env, Executor, FeeOptions, NetworkSpecificRefs,
+    Querier,
+};
+
+/// The network name of environments created by [`MockChain::env`].
+pub const MOCK_NETWORK: &str = "mock";
+
+/// Builds a native contract, in place of wasm that can't be run in process.
+pub type ContractFactory = fn() -> Box<dyn Contract<Empty>>;
+
+/// The in-process chain, owned by the thread that runs it since contracts aren't `Send`.
+struct Chain {
+    app: App,
+    /// Native contracts standing in for wasm bytecode, by checksum.
+    wasm: HashMap<String, ContractFactory>,
+    /// Checksums of stored code, by code ID.
+    checksums: HashMap<u64, String>,
+    /// Instantiate permissions of stored code, by code ID. Code without one is instantiable
+    /// by everybody.
+    access: HashMap<u64, AccessConfig>,
+    /// Receipts of every transaction, by hash.
+    txs: HashMap<String, Value>,
+}
+
+type Job = Box<dyn FnOnce(&mut Chain) + Send>;
+
+/// An in-memory chain backed by `cw-multi-test`, implementing [`Querier`] and [`Executor`]
+/// so tasks can be tested without an LCD.
+///
+/// Wasm can't be executed in process, so uploaded bytecode is matched by checksum with a
+/// native contract registered through [`MockChain::register_wasm`]. Clones share the chain.
+#[derive(Clone)]
+pub struct MockChain {
+    jobs: Sender<Job>,
+    sender: String,
+}
+
+impl Default for MockChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockChain {
+    /// Start an empty chain, sending transactions from the address `sender`, see
+    /// [`MockChain::with_sender`].
+    pub fn new() -> Self {
+        let (jobs, receiver) = channel::<Job>();
+        std::thread::spawn(move || {
+            let mut chain = Chain {
+                app: App::default(),
+                wasm: HashMap::new(),
+                checksums: HashMap::new(),
+                access: HashMap::new(),
+                txs: HashMap::new(),
+            };
+            // Runs until every handle to the chain is dropped.
+            for job in receiver {
+                job(&mut chain);
+            }
+        });
+        Self {
+            jobs,
+            sender: "sender".to_string(),
+        }
+    }
+
+    /// A handle to the same chain that sends transactions from `sender`.
+    pub fn with_sender(&self, sender: impl Into<String>) -> Self {
+        Self {
+            jobs: self.jobs.clone(),
+            sender: sender.into(),
+        }
+    }
+
+    /// The address transactions are sent from.
+    pub fn sender(&self) -> &str {
+        &self.sender
+    }
+
+    /// An environment using this chain for queries and transactions, with empty refs that
+    /// aren't saved to `contracts.json`.
+    pub fn env(&self) -> Env {
+        Env::in_memory(
+            MOCK_NETWORK.to_string(),
+            Box::new(self.clone()),
+            Box::new(self.clone()),
+            NetworkSpecificRefs::default(),
+        )
+    }
+
+    fn run<T: Send + 'static>(&self, job: impl FnOnce(&mut Chain) -> T + Send + 'static) -> T {
+        let (result, receiver) = channel();
+        let job: Job = Box::new(move |chain| {
+            let _ = result.send(job(chain));
+        });
+        self.jobs
+            .send(job)
+            .expect("the mock chain stopped after a contract panicked");
+        receiver
+            .recv()
+            .expect("the mock chain stopped after a contract panicked")
+    }
+
+    /// Run a function with the underlying `cw-multi-test` app, such as to inspect its state.
+    pub fn with_app<T: Send + 'static>(&self, f: impl FnOnce(&mut App) -> T + Send + 'static) -> T {
+        self.run(move |chain| f(&mut chain.app))
+    }
+
+    /// Mint coins to an address.
+    pub fn init_balance(&self, address: impl Into<String>, coins: Vec<Coin>) -> Result<()> {
+        let address = Addr::unchecked(address);
+        self.run(move |chain| {
+            chain.app.init_modules(|router, _, storage| {
+                router.bank.init_balance(storage, &address, coins)
+            })
+        })
+    }
+
+    /// Use a native contract whenever `bytecode` is uploaded.
+    pub fn register_wasm(&self, bytecode: &[u8], contract: ContractFactory) {
+        let checksum = checksum(bytecode);
+        self.run(move |chain| chain.wasm.insert(checksum, contract));
+    }
+
+    /// Store a native contract directly, returning its code ID.
+    pub fn store_native(&self, contract: ContractFactory) -> u64 {
+        self.run(move |chain| {
+            let code_id = chain.app.store_code(contract());
+            chain.checksums.insert(code_id, format!("native-{code_id}"));
+            code_id
+        })
+    }
+
+    /// Execute messages in order, returning the hash of a transaction whose receipt
+    /// has the same shape as an LCD's.
+    ///
+    /// Like a real transaction, the messages succeed or fail together: when one fails, the
+    /// state changes of the others are rolled back and the receipt has a non-zero code. Code
+    /// stored by a failed transaction stays in `cw-multi-test`, but isn't given a checksum, so
+    /// `code_info` doesn't find it.
+    ///
+    /// Instantiate permissions are checked for the `Instantiate` messages of the transaction,
+    /// but not for contracts instantiated by other contracts.
+    fn send(&self, sender: String, msgs: Vec<Tx>) -> Result<String> {
+        self.run(move |chain| {
+            // A missing native contract is a mistake in the test, not a failed transaction.
+            let mut contracts = vec![];
+            for msg in &msgs {
+                if let Tx::Store(bytecode, _) = msg {
+                    let checksum = checksum(bytecode);
+                    let contract = *chain.wasm.get(&checksum).ok_or(anyhow::anyhow!(
+                        "No native contract registered for wasm with checksum {checksum}, use MockChain::register_wasm"
+                    ))?;
+                    contracts.push((checksum, contract));
+                }
+            }
+
+            // Code can't be stored through a `CosmosMsg`, so it's stored before the other
+            // messages run, which `execute_multi` does in a cache that is dropped on error.
+            let mut stored = vec![];
+            let mut access = HashMap::new();
+            let mut unauthorized = None;
+            let mut events = vec![];
+            let mut cosmos_msgs = vec![];
+            let mut contracts = contracts.into_iter();
+            for msg in msgs {
+                match msg {
+                    Tx::Store(_, permission) => {
+                        let (checksum, contract) = contracts.next().unwrap();
+                        let code_id = chain.app.store_code(contract());
+                        stored.push((code_id, checksum));
+                        if let Some(permission) = permission {
+                            access.insert(code_id, permission);
+                        }
+                        events.push(Some(vec![
+                            Event::new("store_code").add_attribute("code_id", code_id.to_string())
+                        ]));
+                    }
+                    Tx::Cosmos(msg) => {
+                        if let CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. }) = &msg {
+                            let permission =
+                                access.get(code_id).or_else(|| chain.access.get(code_id));
+                            if unauthorized.is_none() && !may_instantiate(permission, &sender) {
+                                unauthorized = Some(anyhow::anyhow!(
+                                    "{sender} can not instantiate code {code_id}: unauthorized"
+                                ));
+                            }
+                        }
+                        cosmos_msgs.push(msg);
+                        events.push(None);
+                    }
+                }
+            }
+            let result = match unauthorized {
+                Some(e) => Err(e),
+                None => chain
+                    .app
+                    .execute_multi(Addr::unchecked(&sender), cosmos_msgs),
+            };
+
+            let height = chain.app.block_info().height;
+            let hash = checksum(format!("{}", chain.txs.len()).as_bytes()).to_uppercase();
+            let tx_response = match result {
+                Ok(responses) => {
+                    chain.checksums.extend(stored);
+                    chain.access.extend(access);
+                    let mut responses = responses.into_iter();
+                    let logs = events
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, events)| {
+                            let events = events.unwrap_or_else(|| {
+                                let AppResponse { events, .. } = responses.next().unwrap();
+                                events
+                            });
+                            tx_log(index, &events)
+                        })
+                        .collect::<Vec<_>>();
+                    json!({
+                        "code": 0,
+                        "txhash": hash,
+                        "height": height.to_string(),
+                        "raw_log": "",
+                        "logs": logs,
+                    })
+                }
+                // wasmd reports failed contract calls as code 5 of the wasm codespace.
+                Err(e) => json!({
+                    "code": 5,
+                    "codespace": "wasm",
+                    "txhash": hash,
+                    "height": height.to_string(),
+                    "raw_log": format!("{e:#}"),
+                    "logs": [],
+                }),
+            };
+            chain
+                .txs
+                .insert(hash.clone(), json!({ "tx_response": tx_response }));
+            chain.app.update_block(next_block);
+            Ok(hash)
+        })
+    }
+}
+
+/// The log of one message in a receipt.
+fn tx_log(index: usize, events: &[Event]) -> Value {
+    json!({
+        "msg_index": index,
+        "events": events
+            .iter()
+            .map(|event| json!({
+                "type": event.ty,
+                "attributes": event
+                    .attributes
+                    .iter()
+                    .map(|attr| {
+                        // wasmd reports the address as `_contract_address`.
+                        let key = match attr.key.as_str() {
+                            "_contract_addr" => "_contract_address",
+                            key => key,
+                        };
+                        json!({ "key": key, "value": attr.value })
+                    })
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Whether `sender` may instantiate code stored with `access`.
+fn may_instantiate(access: Option<&AccessConfig>, sender: &str) -> bool {
+    match access {
+        None | Some(AccessConfig::Everybody) => true,
+        Some(AccessConfig::Nobody) => false,
+        Some(AccessConfig::AnyOfAddresses(addresses)) => {
+            addresses.iter().any(|address| address == sender)
+        }
+    }
+}
+
+/// A message in a mock transaction.
+enum Tx {
+    /// Bytecode and the permission to instantiate it.
+    Store(Vec<u8>, Option<AccessConfig>),
+    Cosmos(CosmosMsg),
+}
+
+fn to_coins(coins: Vec<cosmrs::Coin>) -> Vec<Coin> {
+    coins
+        .into_iter()
+        .map(|coin| cosmwasm_std::coin(coin.amount, coin.denom.to_string()))
+        .collect()
+}
+
+#[async_trait]
+impl Querier for MockChain {
+    async fn query<Req, Res>(&self, address: String, message: &Req) -> Result<Res>
+    where
+        Req: Serialize + ?Sized + Sync,
+        Res: for<'de> serde::Deserialize<'de>,
+    {
+        let msg = to_binary(message)?;
+        let response = self.run(move |chain| {
+            chain
+                .app
+                .wrap()
+                .query::<Value>(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: address,
+                    msg,
+                }))
+                .map_err(|e| anyhow::anyhow!(e))
+        })?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    async fn wait_for_transaction(&self, tx_hash: String) -> Result<Value> {
+        let receipt = self.run({
+            let tx_hash = tx_hash.clone();
+            move |chain| chain.txs.get(&tx_hash).cloned()
+        });
+        receipt.ok_or(anyhow::anyhow!("Transaction {tx_hash} not found"))
+    }
+
+    async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        let data = self
+            .run({
+                let address = address.clone();
+                move |chain| chain.app.contract_data(&Addr::unchecked(address))
+            })
+            .map_err(|_| ContractNotFound(address.clone()))?;
+        Ok(ContractInfo {
+            address,
+            code_id: data.code_id as u64,
+            creator: data.creator.to_string(),
+            admin: data.admin.map(|admin| admin.to_string()),
+            label: data.label,
+        })
+    }
+
+    async fn code_info(&self, code_id: u64) -> Result<CodeInfo> {
+        let checksum = self
+            .run(move |chain| chain.checksums.get(&code_id).cloned())
+            .ok_or(anyhow::anyhow!("Code {code_id} not found"))?;
+        Ok(CodeInfo {
+            code_id,
+            creator: self.sender.clone(),
+            checksum,
+        })
+    }
+}
+
+#[async_trait]
+impl Executor for MockChain {
+    async fn execute_smart<Req>(
+        &self,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        _memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = WasmMsg::Execute {
+            contract_addr: address,
+            msg: to_binary(message)?,
+            funds,
+        };
+        self.send(self.sender.clone(), vec![Tx::Cosmos(msg.into())])
+    }
+
+    async fn store_code(&self, bytecode: Vec<u8>, _memo: Option<String>) -> Result<String> {
+        self.send(self.sender.clone(), vec![Tx::Store(bytecode, None)])
+    }
+
+    async fn store_code_with_access(
+        &self,
+        bytecode: Vec<u8>,
+        access: AccessConfig,
+        _memo: Option<String>,
+    ) -> Result<String> {
+        self.send(self.sender.clone(), vec![Tx::Store(bytecode, Some(access))])
+    }
+
+    async fn instantiate<Req>(
+        &self,
+        code_id: u64,
+        msg: &Req,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        _memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = WasmMsg::Instantiate {
+            admin,
+            code_id,
+            msg: to_binary(msg)?,
+            funds,
+            label: label.unwrap_or_default(),
+        };
+        self.send(self.sender.clone(), vec![Tx::Cosmos(msg.into())])
+    }
+
+    async fn migrate<Req>(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: &Req,
+        _memo: Option<String>,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        let msg = WasmMsg::Migrate {
+            contract_addr: address,
+            new_code_id: code_id,
+            msg: to_binary(msg)?,
+        };
+        self.send(self.sender.clone(), vec![Tx::Cosmos(msg.into())])
+    }
+
+    /// Sends directly, as mock contract addresses aren't valid bech32.
+    async fn update_admin(
+        &self,
+        address: String,
+        new_admin: String,
+        _memo: Option<String>,
+    ) -> Result<String> {
+        let msg = WasmMsg::UpdateAdmin {
+            contract_addr: address,
+            admin: new_admin,
+        };
+        self.send(self.sender.clone(), vec![Tx::Cosmos(msg.into())])
+    }
+
+    /// Sends directly, as mock contract addresses aren't valid bech32.
+    async fn clear_admin(&self, address: String, _memo: Option<String>) -> Result<String> {
+        let msg = WasmMsg::ClearAdmin {
+            contract_addr: address,
+        };
+        self.send(self.sender.clone(), vec![Tx::Cosmos(msg.into())])
+    }
+
+    /// Only wasm and bank send messages are supported.
+    async fn execute_any(&self, messages: Vec<Any>, _memo: Option<String>) -> Result<String> {
+        let mut msgs = vec![];
+        for any in messages {
+            let msg = match any.type_url.as_str() {
+                "/cosmwasm.wasm.v1.MsgStoreCode" => {
+                    let (bytecode, access) = messages::decode_store_code(&any)?;
+                    Tx::Store(bytecode, access)
+                }
+                "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+                    let msg = MsgExecuteContract::from_any(&any).map_err(|e| anyhow::anyhow!(e))?;
+                    Tx::Cosmos(
+                        WasmMsg::Execute {
+                            contract_addr: msg.contract.to_string(),
+                            msg: Binary(msg.msg),
+                            funds: to_coins(msg.funds),
+                        }
+                        .into(),
+                    )
+                }
+                "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+                    let msg =
+                        MsgInstantiateContract::from_any(&any).map_err(|e| anyhow::anyhow!(e))?;
+                    Tx::Cosmos(
+                        WasmMsg::Instantiate {
+                            admin: msg.admin.map(|admin| admin.to_string()),
+                            code_id: msg.code_id,
+                            msg: Binary(msg.msg),
+                            funds: to_coins(msg.funds),
+                            label: msg.label.unwrap_or_default(),
+                        }
+                        .into(),
+                    )
+                }
+                "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+                    let msg = MsgMigrateContract::from_any(&any).map_err(|e| anyhow::anyhow!(e))?;
+                    Tx::Cosmos(
+                        WasmMsg::Migrate {
+                            contract_addr: msg.contract.to_string(),
+                            new_code_id: msg.code_id,
+                            msg: Binary(msg.msg),
+                        }
+                        .into(),
+                    )
+                }
+                "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+                    let msg = MsgUpdateAdmin::from_any(&any).map_err(|e| anyhow::anyhow!(e))?;
+                    Tx::Cosmos(
+                        WasmMsg::UpdateAdmin {
+                            contract_addr: msg.contract,
+                            admin: msg.new_admin,
+                        }
+                        .into(),
+                    )
+                }
+                "/cosmwasm.wasm.v1.MsgClearAdmin" => {
+                    let msg = MsgClearAdmin::from_any(&any).map_err(|e| anyhow::anyhow!(e))?;
+                    Tx::Cosmos(
+                        WasmMsg::ClearAdmin {
+                            contract_addr: msg.contract,
+                        }
+                        .into(),
+                    )
+                }
+                "/cosmos.bank.v1beta1.MsgSend" => {
+                    let msg = MsgSend::from_any(&any).map_err(|e| anyhow::anyhow!(e))?;
+                    Tx::Cosmos(
+                        BankMsg::Send {
+                            to_address: msg.to_address.to_string(),
+                            amount: to_coins(msg.amount),
+                        }
+                        .into(),
+                    )
+                }
+                type_url => {
+                    return Err(anyhow::anyhow!(
+                        "{type_url} messages are not supported by the mock chain"
+                    ))
+                }
+            };
+            msgs.push(msg);
+        }
+        self.send(self.sender.clone(), msgs)
+    }
+
+    /// Transactions on the mock chain are free, so `fee` is ignored.
+    async fn execute_smart_with_fee<Req>(
+        &self,
+        address: String,
+        message: &Req,
+        funds: Vec<Coin>,
+        memo: Option<String>,
+        _fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        self.execute_smart(address, message, funds, memo).await
+    }
+
+    /// Transactions on the mock chain are free, so `fee` is ignored.
+    async fn store_code_with_fee(
+        &self,
+        bytecode: Vec<u8>,
+        access: Option<AccessConfig>,
+        _memo: Option<String>,
+        _fee: FeeOptions,
+    ) -> Result<String> {
+        self.send(self.sender.clone(), vec![Tx::Store(bytecode, access)])
+    }
+
+    /// Transactions on the mock chain are free, so `fee` is ignored.
+    async fn instantiate_with_fee<Req>(
+        &self,
+        code_id: u64,
+        msg: &Req,
+        funds: Vec<Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+        memo: Option<String>,
+        _fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        self.instantiate(code_id, msg, funds, label, admin, memo)
+            .await
+    }
+
+    /// Transactions on the mock chain are free, so `fee` is ignored.
+    async fn migrate_with_fee<Req>(
+        &self,
+        address: String,
+        code_id: u64,
+        msg: &Req,
+        memo: Option<String>,
+        _fee: FeeOptions,
+    ) -> Result<String>
+    where
+        Req: Serialize + Sync + Clone,
+    {
+        self.migrate(address, code_id, msg, memo).await
+    }
+
+    /// Transactions on the mock chain are free, so `fee` is ignored.
+    async fn execute_any_with_fee(
+        &self,
+        messages: Vec<Any>,
+        memo: Option<String>,
+        _fee: FeeOptions,
+    ) -> Result<String> {
+        self.execute_any(messages, memo).await
+    }
+
+    /// Sends directly, as mock addresses needn't be valid bech32 for a `MsgSend`.
+    async fn bank_send(
+        &self,
+        to_address: String,
+        amount: Vec<Coin>,
+        _memo: Option<String>,
+    ) -> Result<String> {
+        let msg = BankMsg::Send { to_address, amount };
+        self.send(self.sender.clone(), vec![Tx::Cosmos(msg.into())])
+    }
+
+    fn address(&self) -> String {
+        self.sender.clone()
+    }
+}
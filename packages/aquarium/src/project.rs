@@ -1,13 +1,20 @@
-use std::{collections::HashMap, path::PathBuf, process::Command};
+use std::{collections::HashMap, path::PathBuf, process::Command, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
+use cosmwasm_std::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    account::{AccountWithInfo, SerializableAccount},
+    account::{AccountWithInfo, MultisigAccount, SerializableAccount},
     cli::task::TaskArgs,
-    ContractRefs, Env, Network, QueryClient, SigningClient,
+    client::{
+        offline::{parse_public_key, Multisig},
+        signer::Signer,
+        transport::transport_from_env,
+    },
+    config::{devnet::DevnetConfig, pipeline::Pipeline},
+    ContractRefs, DynExecutor, DynQuerier, Env, GasPrice, Network, QueryClient, SigningClient,
 };
 
 pub const CONFIG_FILE_NAME: &str = "Aquarium.toml";
@@ -92,39 +99,50 @@ impl Project {
     }
 
     pub fn env(&self) -> Result<Env> {
-        let args = TaskArgs::parse();
+        self.env_from_args(&TaskArgs::parse())
+    }
 
+    /// The name and config of the network selected by `args`.
+    pub fn network_from_args(&self, args: &TaskArgs) -> Result<(String, Network)> {
         let network_name = args
             .network
+            .clone()
             .or_else(|| match self.config.default_network {
                 Some(ref n) => Some(n.clone()),
                 None => self.config.networks.keys().next().cloned(),
             })
             .ok_or(anyhow::anyhow!("No networks specified"))?;
-        let network = self
+        let mut network = self
             .config
             .networks
             .get(&network_name)
-            .ok_or(anyhow::anyhow!("Could not find network"))?;
-
-        let account = args.account.or(self.config.accounts.keys().next().cloned());
-        let account = account
-            .map(|a| {
-                self.config
-                    .accounts
-                    .get(&a)
-                    .ok_or(anyhow::anyhow!("Could not find account"))
-            })
-            .transpose()?
-            .ok_or(anyhow::anyhow!("No accounts specified"))?;
-        let account = AccountWithInfo::new(
-            account.clone(),
-            self.config.hd_path.clone(),
-            &network.account_prefix,
-        )?;
+            .ok_or(anyhow::anyhow!("Could not find network"))?
+            .clone();
+        if let Some(transport) = transport_from_env()? {
+            network = network.with_transport(transport);
+        }
+        Ok((network_name, network))
+    }
+
+    pub fn env_from_args(&self, args: &TaskArgs) -> Result<Env> {
+        let (network_name, network) = self.network_from_args(args)?;
+        self.env_on(network_name, &network, args.account.clone())
+    }
+
+    /// An environment on `network`, from [`Project::network_from_args`], sending from
+    /// `account` or the first account. Commands that also call the network themselves use it
+    /// to share one transport with the environment.
+    pub fn env_on(
+        &self,
+        network_name: String,
+        network: &Network,
+        account: Option<String>,
+    ) -> Result<Env> {
+        let account = account.or(self.config.accounts.keys().next().cloned());
+        let account = account.ok_or(anyhow::anyhow!("No accounts specified"))?;
 
-        let querier = QueryClient::new(network.clone());
-        let executor = SigningClient::new(network.clone(), account);
+        let querier: Box<dyn DynQuerier> = Box::new(QueryClient::new(network.clone()));
+        let executor: Box<dyn DynExecutor> = Box::new(self.signing_client(network, &account)?);
 
         let refs_path = self.root.join("contracts.json");
         let refs = ContractRefs::load_or_default(refs_path.clone())?
@@ -133,10 +151,88 @@ impl Project {
             .cloned()
             .unwrap_or_default();
 
-        let env = Env::new(network_name, querier, executor, refs, refs_path)?;
+        let mut env = Env::new(network_name, querier, executor, refs, refs_path)?;
+        // Only funding needs the faucet, so a faucet that can't be loaded doesn't stop the
+        // commands that don't fund anything.
+        if let Some(faucet) = &network.faucet {
+            match self.signing_client(network, faucet) {
+                Ok(client) => env = env.with_faucet(Box::new(client)),
+                Err(e) => {
+                    eprintln!("Could not load faucet account {faucet}, funding will fail: {e}")
+                }
+            }
+        }
 
         Ok(env)
     }
+
+    /// The address of the account or multisig `name` in `Aquarium.toml` on `network`, or
+    /// `name` itself if there is no such account.
+    pub fn address(&self, network: &Network, name: &str) -> Result<String> {
+        if self.config.accounts.contains_key(name) {
+            return Ok(self.account(network, name)?.address.to_string());
+        }
+        match self.multisig(name)? {
+            Some(multisig) => multisig.address(&network.account_prefix),
+            None => Ok(name.to_string()),
+        }
+    }
+
+    /// The account `name` from `Aquarium.toml` with its address on `network`.
+    pub fn account(&self, network: &Network, name: &str) -> Result<AccountWithInfo> {
+        let account = self
+            .config
+            .accounts
+            .get(name)
+            .ok_or(anyhow::anyhow!("Could not find account \"{name}\""))?;
+        AccountWithInfo::new(
+            account.clone(),
+            self.config.hd_path.clone(),
+            &self.root,
+            &network.account_prefix,
+        )
+    }
+
+    /// The signer of the account `name` from `Aquarium.toml`.
+    pub fn signer(&self, name: &str) -> Result<Arc<dyn Signer>> {
+        self.config
+            .accounts
+            .get(name)
+            .ok_or(anyhow::anyhow!("Could not find account \"{name}\""))?
+            .signer(&self.config.hd_path, &self.root)
+    }
+
+    /// The public key of the account `name` from `Aquarium.toml`, or `name` itself as a base64
+    /// public key.
+    pub fn public_key(&self, name: &str) -> Result<cosmrs::crypto::PublicKey> {
+        if self.config.accounts.contains_key(name) {
+            return Ok(self.signer(name)?.public_key());
+        }
+        parse_public_key(name)
+    }
+
+    /// The multisig `name` from `Aquarium.toml`, if there is one.
+    pub fn multisig(&self, name: &str) -> Result<Option<Multisig>> {
+        let Some(MultisigAccount { threshold, members }) = self.config.multisigs.get(name) else {
+            return Ok(None);
+        };
+        let public_keys = members
+            .iter()
+            .map(|member| self.public_key(member))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(Multisig {
+            threshold: *threshold,
+            public_keys,
+        }))
+    }
+
+    /// A client for `network` signing with the account `name` from `Aquarium.toml`.
+    pub fn signing_client(&self, network: &Network, name: &str) -> Result<SigningClient> {
+        Ok(SigningClient::new(
+            network.clone(),
+            self.account(network, name)?,
+        ))
+    }
 }
 
 impl Default for Project {
@@ -156,6 +252,12 @@ pub struct Config {
     pub default_network: Option<String>,
     pub networks: HashMap<String, Network>,
     pub accounts: HashMap<String, SerializableAccount>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub multisigs: HashMap<String, MultisigAccount>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pipelines: HashMap<String, Pipeline>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub devnet: Option<DevnetConfig>,
 }
 
 impl Config {
@@ -181,10 +283,19 @@ impl Default for Config {
                     Network {
                         chain_id: "harpoon-4".to_string(),
                         lcd_addr: "http://localhost:1317".to_string(),
-                        gas_price: 0.00125,
-                        gas_adjustment: 1.25,
+                        gas_price: GasPrice::Fixed {
+                            amount: Decimal::from_ratio(125u128, 100_000u128),
+                            denom: Some("ukuji".to_string()),
+                        },
+                        max_gas_price: None,
+                        gas_adjustment: Decimal::percent(125),
                         gas_denom: "ukuji".to_string(),
                         account_prefix: "kujira".to_string(),
+                        faucet: None,
+                        admin: None,
+                        mainnet: false,
+                        max_fee: None,
+                        transport: None,
                     },
                 ),
                 (
@@ -192,10 +303,19 @@ impl Default for Config {
                     Network {
                         chain_id: "harpoon-4".to_string(),
                         lcd_addr: "https://test-lcd-kujira.mintthemoon.xyz".to_string(), //TODO
-                        gas_price: 0.00125,
-                        gas_adjustment: 1.25,
+                        gas_price: GasPrice::Fixed {
+                            amount: Decimal::from_ratio(125u128, 100_000u128),
+                            denom: Some("ukuji".to_string()),
+                        },
+                        max_gas_price: None,
+                        gas_adjustment: Decimal::percent(125),
                         gas_denom: "ukuji".to_string(),
                         account_prefix: "kujira".to_string(),
+                        faucet: None,
+                        admin: None,
+                        mainnet: false,
+                        max_fee: None,
+                        transport: None,
                     },
                 ),
                 (
@@ -203,14 +323,26 @@ impl Default for Config {
                     Network {
                         chain_id: "kaiyo-1".to_string(),
                         lcd_addr: "https://lcd-kujira.mintthemoon.xyz".to_string(), //TODO
-                        gas_price: 0.00125,
-                        gas_adjustment: 1.25,
+                        gas_price: GasPrice::Fixed {
+                            amount: Decimal::from_ratio(125u128, 100_000u128),
+                            denom: Some("ukuji".to_string()),
+                        },
+                        max_gas_price: None,
+                        gas_adjustment: Decimal::percent(125),
                         gas_denom: "ukuji".to_string(),
                         account_prefix: "kujira".to_string(),
+                        faucet: None,
+                        admin: None,
+                        mainnet: true,
+                        max_fee: None,
+                        transport: None,
                     },
                 ),
             ]),
             accounts: HashMap::new(),
+            multisigs: HashMap::new(),
+            pipelines: HashMap::new(),
+            devnet: None,
             hd_path: "m/44'/118'/0'/0/0".to_string(),
             default_network: Some("devnet".to_string()),
             scripts_path: "scripts".to_string(),
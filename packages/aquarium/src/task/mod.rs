@@ -0,0 +1,222 @@
+use std::{
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+};
+
+use anyhow::Result;
+use clap::{Arg, ArgAction, ArgMatches, Args, Command, CommandFactory, FromArgMatches};
+use serde::{Deserialize, Serialize};
+
+use crate::{cli::task::TaskArgs, project::Project, Env};
+
+pub mod pipeline;
+
+use pipeline::RunLog;
+
+/// Hidden flag used by `aq task list` to query the tasks registered in a scripts binary.
+pub const LIST_TASKS_FLAG: &str = "list-tasks";
+/// Hidden flag used by `aq task resume` to continue the last run from its failed step.
+pub const RESUME_FLAG: &str = "resume";
+/// Embedded in binaries whose main is [`run_tasks`], so `aq` only passes
+/// [`LIST_TASKS_FLAG`] to binaries that understand it.
+pub const REGISTRY_MARKER: &str = "aquarium-task-registry:v1";
+
+pub type TaskFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + 'a>>;
+
+/// A task registered with `#[aquarium::task(name = "...")]`.
+pub struct TaskDefinition {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// Tasks that run before this one, in order.
+    pub depends_on: &'static [&'static str],
+    /// Adds the task's own arguments to its subcommand.
+    pub args: fn(Command) -> Command,
+    pub run: for<'a> fn(&'a mut Env, &'a ArgMatches) -> TaskFuture<'a>,
+}
+
+inventory::collect!(TaskDefinition);
+
+/// Summary of a registered task, as printed by `--list-tasks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskInfo {
+    pub name: String,
+    pub about: String,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+impl From<&TaskDefinition> for TaskInfo {
+    fn from(task: &TaskDefinition) -> Self {
+        Self {
+            name: task.name.to_string(),
+            about: task.about.to_string(),
+            depends_on: task.depends_on.iter().map(|dep| dep.to_string()).collect(),
+        }
+    }
+}
+
+pub fn registered_tasks() -> impl Iterator<Item = &'static TaskDefinition> {
+    inventory::iter::<TaskDefinition>.into_iter()
+}
+
+/// Conversion from a task's return value into the result reported by the task runner.
+///
+/// Tasks may return `()`, or any `Result<T, E>` whose error converts into an [`anyhow::Error`].
+pub trait TaskOutput {
+    fn into_result(self) -> Result<()>;
+}
+
+impl TaskOutput for () {
+    fn into_result(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<T, E> TaskOutput for std::result::Result<T, E>
+where
+    E: Into<anyhow::Error>,
+{
+    fn into_result(self) -> Result<()> {
+        self.map(|_| ()).map_err(Into::into)
+    }
+}
+
+/// Owns the [`Env`] of a running task, and saves its refs when dropped.
+///
+/// This makes sure that contracts stored or instantiated before a panic are still written to
+/// `contracts.json`. Use [`EnvGuard::finish`] on the happy path to surface save errors.
+pub struct EnvGuard {
+    env: Env,
+    saved: bool,
+}
+
+impl EnvGuard {
+    pub fn new(env: Env) -> Self {
+        Self { env, saved: false }
+    }
+
+    /// Save the refs and consume the guard.
+    pub fn finish(mut self) -> Result<()> {
+        self.saved = true;
+        self.env.save_refs()
+    }
+}
+
+impl Deref for EnvGuard {
+    type Target = Env;
+
+    fn deref(&self) -> &Self::Target {
+        &self.env
+    }
+}
+
+impl DerefMut for EnvGuard {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.env
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        if self.saved {
+            return;
+        }
+        if let Err(err) = self.env.save_refs() {
+            eprintln!("Failed to save contract refs: {err}");
+        }
+    }
+}
+
+/// Parse the standard task flags together with the task's own arguments.
+///
+/// Exits the process with a usage message if the arguments are invalid.
+pub fn parse_task_args<A: Args>() -> (TaskArgs, A) {
+    let matches = A::augment_args(TaskArgs::command()).get_matches();
+    TaskArgs::from_arg_matches(&matches)
+        .and_then(|task_args| Ok((task_args, A::from_arg_matches(&matches)?)))
+        .unwrap_or_else(|err| err.exit())
+}
+
+/// Entrypoint for a scripts binary holding multiple registered tasks.
+///
+/// Each task and each pipeline from `Aquarium.toml` becomes a subcommand, so
+/// `scripts <task> [ARGS]` runs a task after its dependencies.
+pub fn run_tasks() -> Result<()> {
+    std::hint::black_box(REGISTRY_MARKER);
+    let pipelines = Project::load()?.config.pipelines;
+
+    let mut command = TaskArgs::command()
+        .arg(
+            Arg::new(LIST_TASKS_FLAG)
+                .long(LIST_TASKS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(true),
+        )
+        .arg(
+            Arg::new(RESUME_FLAG)
+                .long(RESUME_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(true),
+        );
+    for task in registered_tasks() {
+        command = command.subcommand((task.args)(Command::new(task.name).about(task.about)));
+    }
+    for (name, pipeline) in &pipelines {
+        if registered_tasks().any(|task| task.name == name) {
+            return Err(anyhow::anyhow!(
+                "Pipeline \"{name}\" has the same name as a task"
+            ));
+        }
+        command = command.subcommand(Command::new(name).about(pipeline.about.clone()));
+    }
+    let matches = command.clone().get_matches();
+
+    if matches.get_flag(LIST_TASKS_FLAG) {
+        let tasks = registered_tasks().map(TaskInfo::from).collect::<Vec<_>>();
+        println!("{}", serde_json::to_string(&tasks)?);
+        return Ok(());
+    }
+
+    let Some((name, task_matches)) = matches.subcommand() else {
+        command.print_help()?;
+        return Ok(());
+    };
+    let steps = match registered_tasks().find(|task| task.name == name) {
+        Some(task) => pipeline::plan_task(task, task_matches)?,
+        None => pipeline::plan_pipeline(&pipelines[name])?,
+    };
+    let task_args = TaskArgs::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let resume = matches.get_flag(RESUME_FLAG);
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(async {
+            let proj = Project::load()?;
+            let mut env = EnvGuard::new(proj.env_from_args(&task_args)?);
+            let log_path = RunLog::path(&proj.root, &env.network, name);
+            let mut log = if resume {
+                let log = RunLog::load(&log_path)?;
+                if log.steps != RunLog::new(&steps).steps {
+                    return Err(anyhow::anyhow!(
+                        "The steps of \"{name}\" or their arguments changed since the last run, it can't be resumed"
+                    ));
+                }
+                if log.is_complete() {
+                    println!("The last run of \"{name}\" already completed");
+                    return env.finish();
+                }
+                println!(
+                    "Resuming \"{name}\" from task \"{}\"",
+                    log.steps[log.completed].task
+                );
+                log
+            } else {
+                RunLog::new(&steps)
+            };
+            let result = pipeline::run_steps(&mut env, &steps, &mut log, &log_path).await;
+            env.finish()?;
+            result
+        })
+}
@@ -0,0 +1,348 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::{ArgMatches, Command};
+use serde::{Deserialize, Serialize};
+
+use crate::{config::pipeline::Pipeline, Env};
+
+use super::{registered_tasks, TaskDefinition};
+
+/// Directory, relative to the project root, holding the run logs of tasks and pipelines.
+pub const RUNS_DIR: &str = ".aquarium/runs";
+
+/// A task to run as part of a pipeline, with its parsed arguments.
+pub struct Step {
+    pub task: &'static TaskDefinition,
+    pub matches: ArgMatches,
+}
+
+impl Step {
+    fn parse(task: &'static TaskDefinition, args: &[String]) -> Result<Self> {
+        let matches = (task.args)(Command::new(task.name))
+            .try_get_matches_from(std::iter::once(task.name.to_string()).chain(args.to_vec()))
+            .map_err(|err| {
+                anyhow::anyhow!("Invalid arguments for task \"{}\":\n{err}", task.name)
+            })?;
+        Ok(Self { task, matches })
+    }
+
+    /// The arguments of the step as `name=value` pairs sorted by name, defaults included, so
+    /// runs with different arguments can be told apart.
+    pub fn args(&self) -> Vec<String> {
+        let mut args = self
+            .matches
+            .ids()
+            .map(|id| {
+                let values = self
+                    .matches
+                    .get_raw(id.as_str())
+                    .into_iter()
+                    .flatten()
+                    .map(|value| value.to_string_lossy())
+                    .collect::<Vec<_>>();
+                format!("{id}={}", values.join(","))
+            })
+            .collect::<Vec<_>>();
+        args.sort();
+        args
+    }
+}
+
+fn find_task(name: &str) -> Result<&'static TaskDefinition> {
+    registered_tasks()
+        .find(|task| task.name == name)
+        .ok_or(anyhow::anyhow!("Task \"{name}\" not found"))
+}
+
+/// Builds the ordered list of steps, running every dependency before the task that needs it.
+#[derive(Default)]
+struct Planner {
+    steps: Vec<Step>,
+    visiting: Vec<&'static str>,
+}
+
+impl Planner {
+    fn add(&mut self, task: &'static TaskDefinition, matches: Option<ArgMatches>) -> Result<()> {
+        if let Some(planned) = self.steps.iter().find(|step| step.task.name == task.name) {
+            // A task runs once, so it can't be asked for again with other arguments.
+            if let Some(matches) = matches {
+                if (Step { task, matches }).args() != planned.args() {
+                    return Err(anyhow::anyhow!(
+                        "Task \"{}\" is planned twice with different arguments",
+                        task.name
+                    ));
+                }
+            }
+            return Ok(());
+        }
+        if self.visiting.contains(&task.name) {
+            return Err(anyhow::anyhow!(
+                "Dependency cycle: {} -> {}",
+                self.visiting.join(" -> "),
+                task.name
+            ));
+        }
+        self.visiting.push(task.name);
+        for dep in task.depends_on {
+            self.add(find_task(dep)?, None)?;
+        }
+        self.visiting.pop();
+
+        let step = match matches {
+            Some(matches) => Step { task, matches },
+            None => Step::parse(task, &[])?,
+        };
+        self.steps.push(step);
+        Ok(())
+    }
+}
+
+/// Plan a single task and its dependencies. Dependencies run with their default arguments.
+pub fn plan_task(task: &'static TaskDefinition, matches: &ArgMatches) -> Result<Vec<Step>> {
+    let mut planner = Planner::default();
+    planner.add(task, Some(matches.clone()))?;
+    Ok(planner.steps)
+}
+
+/// Plan the steps of a pipeline from `Aquarium.toml`, including their dependencies.
+pub fn plan_pipeline(pipeline: &Pipeline) -> Result<Vec<Step>> {
+    let mut planner = Planner::default();
+    for step in &pipeline.steps {
+        let task = find_task(step.task())?;
+        planner.add(task, Some(Step::parse(task, step.args())?.matches))?;
+    }
+    Ok(planner.steps)
+}
+
+/// Progress of the last run of a task or pipeline on a network.
+///
+/// Used by `aq task resume` to skip the steps that already completed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RunLog {
+    pub steps: Vec<RunStep>,
+    pub completed: usize,
+    pub failed: Option<String>,
+}
+
+/// A step of a run: the task, and the arguments it runs with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStep {
+    pub task: String,
+    pub args: Vec<String>,
+}
+
+impl RunLog {
+    pub fn new(steps: &[Step]) -> Self {
+        Self {
+            steps: steps
+                .iter()
+                .map(|step| RunStep {
+                    task: step.task.name.to_string(),
+                    args: step.args(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn path(root: &Path, network: &str, name: &str) -> PathBuf {
+        root.join(RUNS_DIR)
+            .join(network)
+            .join(format!("{name}.json"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|_| anyhow::anyhow!("No previous run found at {}", path.display()))?;
+        serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(std::fs::write(
+            path,
+            serde_json::to_string_pretty(self).map_err(|e| anyhow::anyhow!(e))?,
+        )?)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed >= self.steps.len()
+    }
+}
+
+/// Run the steps not yet completed in `log`, against one shared [`Env`].
+///
+/// The log and the refs are saved after every step, so a failed run can be resumed.
+pub async fn run_steps(env: &mut Env, steps: &[Step], log: &mut RunLog, path: &Path) -> Result<()> {
+    log.save(path)?;
+    for (i, step) in steps.iter().enumerate().skip(log.completed) {
+        if steps.len() > 1 {
+            println!(
+                "[{}/{}] Running task \"{}\"",
+                i + 1,
+                steps.len(),
+                step.task.name
+            );
+        }
+        match (step.task.run)(env, &step.matches).await {
+            Ok(()) => {
+                log.completed = i + 1;
+                log.failed = None;
+                env.save_refs()?;
+                log.save(path)?;
+            }
+            Err(err) => {
+                log.failed = Some(format!("{err:#}"));
+                log.save(path)?;
+                return Err(err.context(format!("Task \"{}\" failed", step.task.name)));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use clap::Arg;
+
+    use super::*;
+    use crate::{
+        config::pipeline::PipelineStep,
+        mock::MockChain,
+        task::{TaskDefinition, TaskFuture},
+    };
+
+    static STORES: AtomicUsize = AtomicUsize::new(0);
+    static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn no_args(command: Command) -> Command {
+        command
+    }
+
+    fn amount_arg(command: Command) -> Command {
+        command.arg(Arg::new("amount").long("amount").default_value("1"))
+    }
+
+    fn succeed<'a>(_: &'a mut Env, _: &'a ArgMatches) -> TaskFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn count_store<'a>(_: &'a mut Env, _: &'a ArgMatches) -> TaskFuture<'a> {
+        STORES.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Fails the first time it runs.
+    fn flaky<'a>(_: &'a mut Env, _: &'a ArgMatches) -> TaskFuture<'a> {
+        let runs = FLAKY_RUNS.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move {
+            match runs {
+                0 => Err(anyhow::anyhow!("failed on purpose")),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    const fn task(
+        name: &'static str,
+        depends_on: &'static [&'static str],
+        args: fn(Command) -> Command,
+        run: for<'a> fn(&'a mut Env, &'a ArgMatches) -> TaskFuture<'a>,
+    ) -> TaskDefinition {
+        TaskDefinition {
+            name,
+            about: "",
+            depends_on,
+            args,
+            run,
+        }
+    }
+
+    inventory::submit!(task("test_store", &[], amount_arg, count_store));
+    inventory::submit!(task("test_instantiate", &["test_store"], no_args, succeed));
+    inventory::submit!(task("test_cycle_a", &["test_cycle_b"], no_args, succeed));
+    inventory::submit!(task("test_cycle_b", &["test_cycle_a"], no_args, succeed));
+    inventory::submit!(task("test_flaky", &["test_store"], no_args, flaky));
+
+    fn pipeline(steps: &[&str]) -> Pipeline {
+        Pipeline {
+            about: String::new(),
+            steps: steps
+                .iter()
+                .map(|step| {
+                    let mut words = step.split_whitespace().map(str::to_string);
+                    PipelineStep::WithArgs {
+                        task: words.next().unwrap(),
+                        args: words.collect(),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn names(steps: &[Step]) -> Vec<&str> {
+        steps.iter().map(|step| step.task.name).collect()
+    }
+
+    #[test]
+    fn plans_each_task_once_after_its_dependencies() {
+        for steps in [
+            ["test_store", "test_instantiate"],
+            ["test_instantiate", "test_store"],
+        ] {
+            let steps = plan_pipeline(&pipeline(&steps)).unwrap();
+            assert_eq!(names(&steps), ["test_store", "test_instantiate"]);
+        }
+    }
+
+    #[test]
+    fn rejects_a_task_planned_with_other_arguments() {
+        assert!(plan_pipeline(&pipeline(&["test_store --amount 1", "test_instantiate"])).is_ok());
+        assert!(plan_pipeline(&pipeline(&["test_instantiate", "test_store --amount 2"])).is_err());
+    }
+
+    #[test]
+    fn rejects_dependency_cycles() {
+        let err = plan_pipeline(&pipeline(&["test_cycle_a"])).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Dependency cycle: test_cycle_a -> test_cycle_b -> test_cycle_a"
+        );
+    }
+
+    #[test]
+    fn logs_the_arguments_of_each_step() {
+        let log = |steps: &[&str]| RunLog::new(&plan_pipeline(&pipeline(steps)).unwrap());
+        assert_eq!(
+            log(&["test_store"]).steps,
+            log(&["test_store --amount 1"]).steps
+        );
+        assert_ne!(
+            log(&["test_store"]).steps,
+            log(&["test_store --amount 2"]).steps
+        );
+    }
+
+    #[tokio::test]
+    async fn resumes_after_the_completed_steps() {
+        let mut env = MockChain::new().env();
+        let steps = plan_pipeline(&pipeline(&["test_flaky"])).unwrap();
+        let path = std::env::temp_dir().join(format!("aquarium-run-{}.json", std::process::id()));
+
+        let mut log = RunLog::new(&steps);
+        assert!(run_steps(&mut env, &steps, &mut log, &path).await.is_err());
+        let mut log = RunLog::load(&path).unwrap();
+        assert_eq!(log.completed, 1);
+        assert_eq!(log.failed.as_deref(), Some("failed on purpose"));
+
+        run_steps(&mut env, &steps, &mut log, &path).await.unwrap();
+        assert!(RunLog::load(&path).unwrap().is_complete());
+        assert_eq!(STORES.load(Ordering::SeqCst), 1);
+        std::fs::remove_file(path).unwrap();
+    }
+}
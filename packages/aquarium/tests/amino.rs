@@ -0,0 +1,91 @@
+use aquarium::client::{
+    messages::{self, to_amino_json, AccessConfig},
+    offline::amino_sign_doc,
+};
+use cosmrs::proto::cosmos::{base::v1beta1::Coin, tx::v1beta1::Fee};
+use cosmwasm_std::coins;
+use serde_json::json;
+
+const ALICE: &str = "kujira1qyqszqgpqyqszqgpqyqszqgpqyqszqgprmrxqk";
+const BOB: &str = "kujira1qgpqyqszqgpqyqszqgpqyqszqgpqyqszjl9rtq";
+const CAROL: &str = "kujira1qvpsxqcrqvpsxqcrqvpsxqcrqvpsxqcrn0yzpp";
+
+fn execute() -> cosmrs::Any {
+    messages::execute(
+        ALICE,
+        BOB,
+        br#"{"transfer":{"amount":"100"}}"#.to_vec(),
+        coins(1234, "ukuji"),
+    )
+    .unwrap()
+}
+
+#[test]
+fn encodes_execute_contract() {
+    assert_eq!(
+        to_amino_json(&execute()).unwrap(),
+        json!({
+            "type": "wasm/MsgExecuteContract",
+            "value": {
+                "sender": ALICE,
+                "contract": BOB,
+                "msg": { "transfer": { "amount": "100" } },
+                "funds": [{ "denom": "ukuji", "amount": "1234" }],
+            },
+        })
+    );
+}
+
+#[test]
+fn encodes_store_code_with_permission() {
+    let msg = messages::store_code(
+        ALICE,
+        b"YELLOW SUBMARINE".to_vec(),
+        Some(AccessConfig::AnyOfAddresses(vec![CAROL.to_string()])),
+    )
+    .unwrap();
+    assert_eq!(
+        to_amino_json(&msg).unwrap(),
+        json!({
+            "type": "wasm/MsgStoreCode",
+            "value": {
+                "sender": ALICE,
+                "wasm_byte_code": "WUVMTE9XIFNVQk1BUklORQ==",
+                "instantiate_permission": {
+                    "permission": "AnyOfAddresses",
+                    "addresses": [CAROL],
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn writes_sorted_escaped_sign_docs() {
+    let fee = Fee {
+        amount: vec![Coin {
+            denom: "ukuji".to_string(),
+            amount: "5000".to_string(),
+        }],
+        gas_limit: 200000,
+        payer: String::new(),
+        granter: String::new(),
+    };
+    let doc = amino_sign_doc("kaiyo-1", 7, 3, &fee, &[execute()], "<deploy> & test", 0).unwrap();
+    assert_eq!(
+        doc,
+        format!(
+            concat!(
+                r#"{{"account_number":"7","chain_id":"kaiyo-1","#,
+                r#""fee":{{"amount":[{{"amount":"5000","denom":"ukuji"}}],"gas":"200000"}},"#,
+                r#""memo":"\u003cdeploy\u003e \u0026 test","#,
+                r#""msgs":[{{"type":"wasm/MsgExecuteContract","value":{{"contract":"{bob}","#,
+                r#""funds":[{{"amount":"1234","denom":"ukuji"}}],"#,
+                r#""msg":{{"transfer":{{"amount":"100"}}}},"sender":"{alice}"}}}}],"#,
+                r#""sequence":"3"}}"#
+            ),
+            alice = ALICE,
+            bob = BOB
+        )
+    );
+}
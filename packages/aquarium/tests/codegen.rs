@@ -0,0 +1,15 @@
+use std::path::Path;
+
+use aquarium::codegen::{load_schemas, rust};
+
+#[test]
+fn generates_rust_client_from_schema() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/counter");
+    let schemas = load_schemas(&[fixtures.join("schema")]).unwrap();
+    assert_eq!(schemas.len(), 1);
+
+    let expected = std::fs::read_to_string(fixtures.join("counter.rs")).unwrap();
+    assert_eq!(rust::module_name(&schemas[0]), "counter");
+    assert_eq!(rust::client_name(&schemas[0]), "CounterClient");
+    assert_eq!(rust::generate(&schemas[0]), expected);
+}
@@ -0,0 +1,68 @@
+//! A counter contract shared by the tests that run on a mock chain.
+
+// Each test crate uses a different part of this module.
+#![allow(dead_code)]
+
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, MessageInfo, Response, StdError, StdResult, Storage,
+};
+use serde::{Deserialize, Serialize};
+
+use aquarium::mock::{Contract, ContractWrapper};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Increment {},
+    /// Increments, then fails.
+    Fail {},
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Count {},
+}
+
+fn load_count(storage: &dyn Storage) -> u64 {
+    storage
+        .get(b"count")
+        .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+        .unwrap_or_default()
+}
+
+fn instantiate(
+    _deps: DepsMut,
+    _env: cosmwasm_std::Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: cosmwasm_std::Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    let count = load_count(deps.storage) + 1;
+    deps.storage.set(b"count", &count.to_be_bytes());
+    match msg {
+        ExecuteMsg::Increment {} => Ok(Response::new().add_attribute("count", count.to_string())),
+        ExecuteMsg::Fail {} => Err(StdError::generic_err("failed on purpose")),
+    }
+}
+
+fn query(deps: Deps, _env: cosmwasm_std::Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Count {} => to_binary(&load_count(deps.storage)),
+    }
+}
+
+pub fn counter() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
@@ -0,0 +1,172 @@
+use aquarium::{
+    mock::{Contract, ContractWrapper, MockChain},
+    utils::{ensure_tx_success, parse_instantiated_address},
+    ContractClient, ContractHandle, Executor, Querier,
+};
+use cosmwasm_std::{
+    coins, from_slice, to_binary, to_vec, Binary, Deps, DepsMut, Empty, MessageInfo, Response,
+    StdResult, Storage,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ContractClient)]
+#[serde(rename_all = "snake_case")]
+#[contract_client(name = "LedgerExecute")]
+pub enum ExecuteMsg {
+    /// Clears the ledger.
+    Reset,
+    Set(u64),
+    /// Adds `funds` and the attached funds to the total.
+    Deposit {
+        memo: String,
+        funds: u64,
+    },
+    /// Fields named like the generated parameters.
+    Record {
+        executor: String,
+        querier: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ContractClient)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    #[returns(u64)]
+    Total {},
+    #[returns(Vec<String>)]
+    Log {},
+    #[returns(Option<String>)]
+    Find(String),
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Ledger {
+    total: u64,
+    log: Vec<String>,
+}
+
+fn load(storage: &dyn Storage) -> Ledger {
+    storage
+        .get(b"ledger")
+        .map(|bytes| from_slice(&bytes).unwrap())
+        .unwrap_or_default()
+}
+
+fn instantiate(
+    _deps: DepsMut,
+    _env: cosmwasm_std::Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: cosmwasm_std::Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    let mut ledger = load(deps.storage);
+    match msg {
+        ExecuteMsg::Reset => ledger = Ledger::default(),
+        ExecuteMsg::Set(total) => ledger.total = total,
+        ExecuteMsg::Deposit { memo, funds } => {
+            let attached = info
+                .funds
+                .iter()
+                .map(|coin| coin.amount.u128() as u64)
+                .sum::<u64>();
+            ledger.total += funds + attached;
+            ledger.log.push(memo);
+        }
+        ExecuteMsg::Record { executor, querier } => {
+            ledger.log.push(format!("{executor}/{querier}"))
+        }
+    }
+    deps.storage.set(b"ledger", &to_vec(&ledger)?);
+    Ok(Response::new())
+}
+
+fn query(deps: Deps, _env: cosmwasm_std::Env, msg: QueryMsg) -> StdResult<Binary> {
+    let ledger = load(deps.storage);
+    match msg {
+        QueryMsg::Total {} => to_binary(&ledger.total),
+        QueryMsg::Log {} => to_binary(&ledger.log),
+        QueryMsg::Find(prefix) => to_binary(
+            &ledger
+                .log
+                .into_iter()
+                .find(|entry| entry.starts_with(&prefix)),
+        ),
+    }
+}
+
+fn ledger() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+async fn ok(chain: &MockChain, hash: String) {
+    ensure_tx_success(&chain.wait_for_transaction(hash).await.unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn calls_generated_methods() {
+    let chain = MockChain::new();
+    chain
+        .init_balance(chain.sender(), coins(100, "ukuji"))
+        .unwrap();
+    let code_id = chain.store_native(ledger);
+    let hash = chain
+        .instantiate(
+            code_id,
+            &InstantiateMsg {},
+            vec![],
+            Some("ledger".to_string()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let receipt = chain.wait_for_transaction(hash).await.unwrap();
+    let ledger = ContractHandle::new(parse_instantiated_address(&receipt).unwrap());
+
+    // The traits are named by `#[contract_client(name)]` or after the enum.
+    ok(
+        &chain,
+        LedgerExecute::set(&ledger, &chain, 5, vec![])
+            .await
+            .unwrap(),
+    )
+    .await;
+    let hash = ledger
+        .deposit(&chain, "first".to_string(), 10, coins(7, "ukuji"))
+        .await
+        .unwrap();
+    ok(&chain, hash).await;
+    let hash = ledger
+        .record(&chain, "alice".to_string(), "bob".to_string(), vec![])
+        .await
+        .unwrap();
+    ok(&chain, hash).await;
+
+    assert_eq!(QueryMsgClient::total(&ledger, &chain).await.unwrap(), 22);
+    assert_eq!(
+        ledger.log(&chain).await.unwrap(),
+        ["first".to_string(), "alice/bob".to_string()]
+    );
+    assert_eq!(
+        ledger.find(&chain, "ali".to_string()).await.unwrap(),
+        Some("alice/bob".to_string())
+    );
+    assert_eq!(
+        ledger.find(&chain, "carol".to_string()).await.unwrap(),
+        None
+    );
+
+    ok(&chain, ledger.reset(&chain, vec![]).await.unwrap()).await;
+    assert_eq!(ledger.total(&chain).await.unwrap(), 0);
+}
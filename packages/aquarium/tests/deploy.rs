@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use aquarium::{
+    deploy::{apply, plan, Action, Manifest},
+    mock::MockChain,
+    Env, Querier,
+};
+
+mod common;
+
+use common::counter;
+
+const WASM: &[u8] = b"counter wasm";
+
+/// Write a manifest deploying one counter, with `admin` as the YAML of its admin settings.
+fn manifest(name: &str, admin: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aquarium-deploy-{}-{name}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("counter.wasm"), WASM).unwrap();
+    let path = dir.join("deploy.yaml");
+    std::fs::write(
+        &path,
+        format!(
+            "contracts:
+  - name: counter
+    wasm: counter.wasm
+    instances:
+      - label: counter
+        msg: {{}}
+{admin}"
+        ),
+    )
+    .unwrap();
+    path
+}
+
+async fn admin(env: &Env) -> Option<String> {
+    let address = env.refs.contracts["counter"].instances[0].address.clone();
+    env.querier.contract_info(address).await.unwrap().admin
+}
+
+#[tokio::test]
+async fn leaves_admin_alone_unless_cleared() {
+    let chain = MockChain::new();
+    chain.register_wasm(WASM, counter);
+    let mut env = chain.env();
+
+    let with_admin = Manifest::load(&manifest("admin", "        admin: ${account}")).unwrap();
+    apply(&mut env, &with_admin).await.unwrap();
+    assert_eq!(admin(&env).await.as_deref(), Some(chain.sender()));
+
+    let without_admin = Manifest::load(&manifest("none", "")).unwrap();
+    assert!(plan(&env, &without_admin).await.unwrap().actions.is_empty());
+
+    let clear = Manifest::load(&manifest("clear", "        clear_admin: true")).unwrap();
+    let actions = plan(&env, &clear).await.unwrap().actions;
+    assert!(matches!(
+        actions.as_slice(),
+        [Action::UpdateAdmin {
+            to: None,
+            preview: None,
+            ..
+        }]
+    ));
+    apply(&mut env, &clear).await.unwrap();
+    assert_eq!(admin(&env).await, None);
+}
+
+#[test]
+fn rejects_admin_with_clear_admin() {
+    let path = manifest(
+        "both",
+        "        admin: ${account}\n        clear_admin: true",
+    );
+    assert!(Manifest::load(&path).is_err());
+}
@@ -0,0 +1,99 @@
+//! Generated by `aq codegen rust` from the schema of counter 0.1.0. Do not edit.
+
+#![allow(clippy::all, dead_code, non_snake_case, unused_imports)]
+
+use ::aquarium::internal::{cosmwasm_std, serde, serde_json, AnyhowResult};
+use ::aquarium::{Executor, Querier};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "::aquarium::internal::serde")]
+pub enum ExecuteMsg {
+    #[serde(rename = "increment")]
+    Increment {
+    },
+    /// Set the count, only the owner may reset.
+    #[serde(rename = "reset")]
+    Reset {
+        count: i32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "::aquarium::internal::serde")]
+pub struct GetCountResponse {
+    pub count: i32,
+    pub owner: cosmwasm_std::Addr,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "::aquarium::internal::serde")]
+pub struct InstantiateMsg {
+    pub count: i32,
+    /// Resets are rejected above this count.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxCount: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(crate = "::aquarium::internal::serde")]
+pub enum QueryMsg {
+    #[serde(rename = "get_count")]
+    GetCount {
+    },
+}
+
+/// Client for an instance of the counter contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterClient {
+    pub address: String,
+}
+
+impl CounterClient {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+        }
+    }
+
+    /// Send a WASM instantiate, returning the transaction hash.
+    pub async fn instantiate(
+        executor: &impl Executor,
+        code_id: u64,
+        msg: &InstantiateMsg,
+        funds: Vec<cosmwasm_std::Coin>,
+        label: Option<String>,
+        admin: Option<String>,
+    ) -> AnyhowResult<String> {
+        executor
+            .instantiate(code_id, msg, funds, label, admin, None)
+            .await
+    }
+
+    pub async fn increment(
+        &self,
+        executor: &impl Executor,
+        funds: Vec<cosmwasm_std::Coin>,
+    ) -> AnyhowResult<String> {
+        executor
+            .execute_smart(self.address.clone(), &ExecuteMsg::Increment {  }, funds, None)
+            .await
+    }
+
+    /// Set the count, only the owner may reset.
+    pub async fn reset(
+        &self,
+        executor: &impl Executor, count: i32,
+        funds: Vec<cosmwasm_std::Coin>,
+    ) -> AnyhowResult<String> {
+        executor
+            .execute_smart(self.address.clone(), &ExecuteMsg::Reset { count }, funds, None)
+            .await
+    }
+
+    pub async fn get_count(
+        &self,
+        querier: &impl Querier,
+    ) -> AnyhowResult<GetCountResponse> {
+        querier.query(self.address.clone(), &QueryMsg::GetCount {  }).await
+    }
+}
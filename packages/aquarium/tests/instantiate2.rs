@@ -0,0 +1,103 @@
+use aquarium::utils::{predict_address, predict_address_with_msg};
+use serde_json::{json, Value};
+
+// Vectors of wasmd's BuildContractAddressPredictable, from cosmjs#1253.
+const CHECKSUM_1: &str = "13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5";
+const CHECKSUM_2: &str = "1da6c16de2cbaf7ad8cbb66f0925ba33f5c278cb2491762d04658c1480ea229b";
+const CREATOR_20: &str = "purple1nxvenxve42424242hwamhwamenxvenxvhxf2py";
+const CREATOR_32: &str = "purple1nxvenxve42424242hwamhwamenxvenxvmhwamhwaamhwamhwlllsatsy6m";
+const LONG_SALT: &str = "aabbccddeeffffeeddbbccddaa66551155aaaabbcc787878789900aabbccddeeffffeeddbbccddaa66551155aaaabbcc787878789900aabbbbcc221100acadae";
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn nested_msg() -> Value {
+    json!({ "some": 123, "structure": { "nested": ["ok", true] } })
+}
+
+#[test]
+fn predicts_wasmd_addresses() {
+    let vectors = [
+        (
+            CHECKSUM_1,
+            CREATOR_20,
+            "61",
+            "purple1t6r960j945lfv8mhl4mage2rg97w63xeynwrupum2s2l7em4lprs9ce5hk",
+        ),
+        (
+            CHECKSUM_1,
+            CREATOR_20,
+            LONG_SALT,
+            "purple1jwzvvfyvpwchrccxl476pxf7c83qawsqv3f2820q0zyrav6eg4jqdcq7gc",
+        ),
+        (
+            CHECKSUM_1,
+            CREATOR_32,
+            "61",
+            "purple1juj7jn6j3k9h35euyhealntquc2zmzlxp2ek76jmtypkl4g4vrdsfwmwxk",
+        ),
+        (
+            CHECKSUM_2,
+            CREATOR_32,
+            LONG_SALT,
+            "purple1mzhc26n2qjzj6xdkg7kk6sekavnwqalhgzh0rgpnrke562v63pdq8grp8q",
+        ),
+    ];
+    for (checksum, creator, salt, expected) in vectors {
+        assert_eq!(
+            predict_address(checksum, creator, &hex(salt)).unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn predicts_wasmd_addresses_with_msg() {
+    let vectors = [
+        (
+            CHECKSUM_1,
+            CREATOR_20,
+            "61",
+            json!({}),
+            "purple1px25n9sgj3a99q0zcl4awx7my6s6mxqegmdd2lmvf5lwxh080q6suttktr",
+        ),
+        (
+            CHECKSUM_1,
+            CREATOR_20,
+            "61",
+            nested_msg(),
+            "purple1svexu428ywc4htrxfn4tezjcsl38qqata8aany4033auafr529ns4v254c",
+        ),
+        (
+            CHECKSUM_1,
+            CREATOR_32,
+            LONG_SALT,
+            nested_msg(),
+            "purple15rgvjs4d43hnuhnuyvgkcnzz5f8fdc9twh6ndy8v9577zcr8cags40l9dt",
+        ),
+        (
+            CHECKSUM_2,
+            CREATOR_20,
+            LONG_SALT,
+            json!({}),
+            "purple1ywsjzf3mlns9c9z0ftux70v2n7rac4hem3ydhnlu33dxzndycesssc7x2m",
+        ),
+    ];
+    for (checksum, creator, salt, msg, expected) in vectors {
+        assert_eq!(
+            predict_address_with_msg(checksum, creator, &hex(salt), &msg).unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_salts() {
+    assert!(predict_address(CHECKSUM_1, CREATOR_20, &[]).is_err());
+    assert!(predict_address(CHECKSUM_1, CREATOR_20, &[0; 65]).is_err());
+    assert!(predict_address(CHECKSUM_1, CREATOR_20, &[0; 64]).is_ok());
+}
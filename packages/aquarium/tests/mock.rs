@@ -0,0 +1,194 @@
+use aquarium::{
+    client::messages,
+    deploy::checksum,
+    mock::MockChain,
+    utils::{ensure_tx_success, parse_code_id, parse_instantiated_address},
+    ContractNotFound, Executor, Querier,
+};
+use cosmwasm_std::coins;
+
+mod common;
+
+use common::{counter, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+const ALICE: &str = "kujira1qyqszqgpqyqszqgpqyqszqgpqyqszqgprmrxqk";
+const BOB: &str = "kujira1qgpqyqszqgpqyqszqgpqyqszqgpqyqszjl9rtq";
+const CAROL: &str = "kujira1qvpsxqcrqvpsxqcrqvpsxqcrqvpsxqcrn0yzpp";
+
+async fn instantiate_counter(chain: &MockChain) -> String {
+    let code_id = chain.store_native(counter);
+    let hash = chain
+        .instantiate(
+            code_id,
+            &InstantiateMsg {},
+            vec![],
+            Some("counter".to_string()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let receipt = chain.wait_for_transaction(hash).await.unwrap();
+    parse_instantiated_address(&receipt).unwrap()
+}
+
+async fn balance(chain: &MockChain, address: &str) -> u128 {
+    let address = address.to_string();
+    chain
+        .with_app(move |app| app.wrap().query_balance(address, "ukuji"))
+        .unwrap()
+        .amount
+        .u128()
+}
+
+#[tokio::test]
+async fn executes_and_queries_contracts() {
+    let chain = MockChain::new();
+    let address = instantiate_counter(&chain).await;
+
+    for _ in 0..2 {
+        let hash = chain
+            .execute_smart(address.clone(), &ExecuteMsg::Increment {}, vec![], None)
+            .await
+            .unwrap();
+        ensure_tx_success(&chain.wait_for_transaction(hash).await.unwrap()).unwrap();
+    }
+    let count: u64 = chain.query(address, &QueryMsg::Count {}).await.unwrap();
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn failed_execute_returns_failed_receipt() {
+    let chain = MockChain::new();
+    let address = instantiate_counter(&chain).await;
+
+    let hash = chain
+        .execute_smart(address.clone(), &ExecuteMsg::Fail {}, vec![], None)
+        .await
+        .unwrap();
+    let receipt = chain.wait_for_transaction(hash).await.unwrap();
+    assert_eq!(receipt["tx_response"]["code"], 5);
+    assert!(receipt["tx_response"]["raw_log"]
+        .as_str()
+        .unwrap()
+        .contains("failed on purpose"));
+    assert!(ensure_tx_success(&receipt).is_err());
+
+    let count: u64 = chain.query(address, &QueryMsg::Count {}).await.unwrap();
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn rolls_back_every_message_of_a_failed_transaction() {
+    let chain = MockChain::new().with_sender(ALICE);
+    chain.init_balance(ALICE, coins(100, "ukuji")).unwrap();
+
+    let hash = chain
+        .execute_any(
+            vec![
+                messages::send(ALICE, BOB, coins(60, "ukuji")).unwrap(),
+                messages::send(ALICE, CAROL, coins(60, "ukuji")).unwrap(),
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+    let receipt = chain.wait_for_transaction(hash).await.unwrap();
+    assert_ne!(receipt["tx_response"]["code"], 0);
+    assert_eq!(balance(&chain, ALICE).await, 100);
+    assert_eq!(balance(&chain, BOB).await, 0);
+
+    let hash = chain
+        .execute_any(
+            vec![messages::send(ALICE, BOB, coins(60, "ukuji")).unwrap()],
+            None,
+        )
+        .await
+        .unwrap();
+    ensure_tx_success(&chain.wait_for_transaction(hash).await.unwrap()).unwrap();
+    assert_eq!(balance(&chain, ALICE).await, 40);
+    assert_eq!(balance(&chain, BOB).await, 60);
+}
+
+#[tokio::test]
+async fn code_stored_by_a_failed_transaction_is_not_found() {
+    let chain = MockChain::new().with_sender(ALICE);
+    let wasm = b"counter wasm".to_vec();
+    chain.register_wasm(&wasm, counter);
+
+    let hash = chain
+        .execute_any(
+            vec![
+                messages::store_code(ALICE, wasm.clone(), None).unwrap(),
+                messages::send(ALICE, BOB, coins(1, "ukuji")).unwrap(),
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+    let receipt = chain.wait_for_transaction(hash).await.unwrap();
+    assert!(ensure_tx_success(&receipt).is_err());
+    assert!(chain.code_info(1).await.is_err());
+
+    let hash = chain.store_code(wasm.clone(), None).await.unwrap();
+    let receipt = chain.wait_for_transaction(hash).await.unwrap();
+    let code_id = parse_code_id(&receipt).unwrap();
+    assert_eq!(
+        chain.code_info(code_id).await.unwrap().checksum,
+        checksum(&wasm)
+    );
+}
+
+#[tokio::test]
+async fn enforces_instantiate_permissions() {
+    let chain = MockChain::new().with_sender(ALICE);
+    let wasm = b"counter wasm".to_vec();
+    chain.register_wasm(&wasm, counter);
+
+    let access = messages::AccessConfig::AnyOfAddresses(vec![BOB.to_string()]);
+    let hash = chain
+        .execute_any(
+            vec![messages::store_code(ALICE, wasm, Some(access)).unwrap()],
+            None,
+        )
+        .await
+        .unwrap();
+    let code_id = parse_code_id(&chain.wait_for_transaction(hash).await.unwrap()).unwrap();
+
+    let instantiate = |chain: MockChain| async move {
+        let hash = chain
+            .instantiate(
+                code_id,
+                &InstantiateMsg {},
+                vec![],
+                Some("counter".to_string()),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        chain.wait_for_transaction(hash).await.unwrap()
+    };
+    let receipt = instantiate(chain.clone()).await;
+    assert!(receipt["tx_response"]["raw_log"]
+        .as_str()
+        .unwrap()
+        .contains("unauthorized"));
+    ensure_tx_success(&instantiate(chain.with_sender(BOB)).await).unwrap();
+}
+
+#[tokio::test]
+async fn uploading_unregistered_wasm_is_an_error() {
+    let chain = MockChain::new();
+    assert!(chain.store_code(b"unknown".to_vec(), None).await.is_err());
+}
+
+#[tokio::test]
+async fn missing_contracts_are_not_found() {
+    let chain = MockChain::new();
+    let err = chain
+        .contract_info("contract9".to_string())
+        .await
+        .unwrap_err();
+    assert!(err.is::<ContractNotFound>());
+}
@@ -0,0 +1,26 @@
+use std::str::FromStr;
+
+use aquarium::Network;
+use cosmwasm_std::Decimal;
+
+fn network(max_gas_price: &str) -> Network {
+    toml::from_str(&format!(
+        r#"
+chain_id = "kaiyo-1"
+lcd_addr = "https://lcd.kaiyo.kujira.setten.io"
+gas_price = "auto"
+max_gas_price = {max_gas_price}
+gas_adjustment = 1.3
+gas_denom = "ukuji"
+account_prefix = "kujira"
+"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn loads_max_gas_price_as_string_or_number() {
+    let expected = Some(Decimal::from_str("0.0125").unwrap());
+    assert_eq!(network("\"0.0125\"").max_gas_price, expected);
+    assert_eq!(network("0.0125").max_gas_price, expected);
+}
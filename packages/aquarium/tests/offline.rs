@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use aquarium::client::offline::{parse_public_key, Multisig, OfflineTx};
+use cosmwasm_std::Binary;
+
+// The members of cosmjs' multisig tests, and the addresses of their LegacyAminoPubKey as
+// computed by a separate implementation of the amino encoding.
+const MEMBERS: [&str; 3] = [
+    "A4y1mO5UEw00+OCBjneHqgYTmg4tACbK22YrVc8WhZpn",
+    "ApBvG9lRbIzTtSY5MiyAG/hyTB+l6HjA4yub1sC7iw9o",
+    "A8yTUZ1htobabw6M/5Qx41a0X5EGPtb4H3nd2JiFiADz",
+];
+
+fn multisig(threshold: u32) -> Multisig {
+    Multisig {
+        threshold,
+        public_keys: MEMBERS
+            .iter()
+            .map(|key| parse_public_key(key).unwrap())
+            .collect(),
+    }
+}
+
+#[test]
+fn derives_multisig_addresses() {
+    let vectors = [
+        (1, "wasm1hez3rmcu96cqv8puvk034yj27vmtgtkzha2ypg"),
+        (2, "wasm1pzf2wlat97n7rykrk7e8g8nxste6hde0r8jqsy"),
+        (3, "wasm10t429d85t5pjpk0snzdzl8wcrhe6rqy860vqmr"),
+    ];
+    for (threshold, expected) in vectors {
+        assert_eq!(multisig(threshold).address("wasm").unwrap(), expected);
+    }
+}
+
+fn tx() -> OfflineTx {
+    OfflineTx {
+        chain_id: "kaiyo-1".to_string(),
+        account_number: 7,
+        sequence: 3,
+        address: multisig(2).address("kujira").unwrap(),
+        body_bytes: Binary(vec![1, 2, 3]),
+        auth_info_bytes: Binary(vec![4, 5, 6]),
+        signers: MEMBERS[..2]
+            .iter()
+            .map(|key| Binary::from_base64(key).unwrap())
+            .collect(),
+        multisig: true,
+        signatures: BTreeMap::new(),
+        amino_sign_doc: None,
+    }
+}
+
+fn signed_by(member: &str) -> OfflineTx {
+    let mut tx = tx();
+    tx.signatures
+        .insert(member.to_string(), Binary(vec![0; 64]));
+    tx
+}
+
+#[test]
+fn merges_signatures_of_the_same_transaction() {
+    let mut tx = signed_by(MEMBERS[0]);
+    tx.merge(signed_by(MEMBERS[1])).unwrap();
+    assert!(tx.missing_signatures().is_empty());
+}
+
+#[test]
+fn refuses_to_merge_different_transactions() {
+    let changes: [fn(&mut OfflineTx); 4] = [
+        |tx| tx.chain_id = "harpoon-4".to_string(),
+        |tx| tx.account_number = 8,
+        |tx| tx.signers.reverse(),
+        |tx| tx.body_bytes = Binary(vec![1, 2, 4]),
+    ];
+    for change in changes {
+        let mut other = signed_by(MEMBERS[1]);
+        change(&mut other);
+        assert!(signed_by(MEMBERS[0]).merge(other).is_err());
+    }
+}
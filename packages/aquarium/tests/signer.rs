@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use aquarium::client::signer::{CommandSigner, LocalSigner, RemoteSigner, Signer, SignerService};
+use async_trait::async_trait;
+use cosmrs::crypto::PublicKey;
+use cosmwasm_std::Binary;
+
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+const PATH: &str = "m/44'/118'/0'/0/0";
+const SIGN_BYTES: &[u8] = b"sign bytes";
+
+fn local() -> LocalSigner {
+    LocalSigner::from_mnemonic(MNEMONIC, PATH).unwrap()
+}
+
+fn other() -> LocalSigner {
+    LocalSigner::from_mnemonic(MNEMONIC, "m/44'/118'/0'/0/1").unwrap()
+}
+
+/// Claims one key but signs with another.
+#[derive(Debug)]
+struct Impostor;
+
+#[async_trait]
+impl Signer for Impostor {
+    fn public_key(&self) -> PublicKey {
+        local().public_key()
+    }
+
+    async fn sign(&self, bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        other().sign(bytes).await
+    }
+}
+
+fn remote(service: Arc<dyn Signer>) -> RemoteSigner {
+    RemoteSigner::new("http://signer", local().public_key())
+        .with_transport(Arc::new(SignerService::new(service)))
+}
+
+#[tokio::test]
+async fn signs_through_a_signing_service() {
+    let signature = remote(Arc::new(local())).sign(SIGN_BYTES).await.unwrap();
+    assert_eq!(signature, local().sign(SIGN_BYTES).await.unwrap());
+}
+
+#[tokio::test]
+async fn rejects_signatures_by_another_key() {
+    assert!(remote(Arc::new(Impostor)).sign(SIGN_BYTES).await.is_err());
+}
+
+/// A signer command that ignores its request and answers with `signature`.
+#[cfg(unix)]
+fn command(signature: &[u8]) -> CommandSigner {
+    let script = format!(
+        "cat > /dev/null; echo '{{\"signature\":\"{}\"}}'",
+        Binary(signature.to_vec()).to_base64()
+    );
+    CommandSigner::new(
+        &["sh".to_string(), "-c".to_string(), script],
+        local().public_key(),
+    )
+    .unwrap()
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn checks_signatures_of_signer_commands() {
+    let signature = local().sign(SIGN_BYTES).await.unwrap();
+    assert_eq!(
+        command(&signature).sign(SIGN_BYTES).await.unwrap(),
+        signature
+    );
+    assert!(command(&signature).sign(b"other bytes").await.is_err());
+    assert!(command(&signature[..63]).sign(SIGN_BYTES).await.is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn rejects_keystores_readable_by_others() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::temp_dir().join(format!("aquarium-keystore-{}", std::process::id()));
+    std::fs::write(&path, "01".repeat(32)).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    assert!(LocalSigner::from_keystore(&path).is_err());
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    assert!(LocalSigner::from_keystore(&path).is_ok());
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,85 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use anyhow::Result;
+use aquarium::{RecordingTransport, ReplayTransport, Transport};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+const LCD: &str = "http://localhost:1317";
+
+/// Stands in for an LCD whose block height goes up on every request.
+#[derive(Debug, Default)]
+struct Lcd {
+    height: AtomicU64,
+}
+
+#[async_trait]
+impl Transport for Lcd {
+    async fn get(&self, lcd_addr: &str, path: &str) -> Result<Value> {
+        assert_eq!(lcd_addr, LCD);
+        let height = self.height.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(json!({ "path": path, "height": height }))
+    }
+
+    async fn post(&self, lcd_addr: &str, path: &str, body: Value) -> Result<Value> {
+        assert_eq!(lcd_addr, LCD);
+        Ok(json!({ "path": path, "echo": body }))
+    }
+}
+
+fn fixture(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("aquarium-{}", std::process::id()))
+        .join(format!("{name}.json"))
+}
+
+#[tokio::test]
+async fn replays_recorded_exchanges() {
+    let path = fixture("round-trip");
+    let recorder = RecordingTransport::new(&path).with_transport(Arc::new(Lcd::default()));
+    let mut recorded = vec![];
+    for _ in 0..2 {
+        recorded.push(recorder.get(LCD, "blocks/latest").await.unwrap());
+    }
+    recorded.push(
+        recorder
+            .post(LCD, "txs", json!({ "tx_bytes": "AA==" }))
+            .await
+            .unwrap(),
+    );
+    recorder.finish().unwrap();
+    assert!(!path.with_extension("json.tmp").exists());
+
+    let replay = ReplayTransport::load(&path).unwrap();
+    assert_eq!(replay.get(LCD, "blocks/latest").await.unwrap(), recorded[0]);
+    assert_eq!(replay.get(LCD, "blocks/latest").await.unwrap(), recorded[1]);
+    // Polling past the recording keeps getting the last response.
+    assert_eq!(replay.get(LCD, "blocks/latest").await.unwrap(), recorded[1]);
+    assert_eq!(
+        replay
+            .post(LCD, "txs", json!({ "tx_bytes": "AA==" }))
+            .await
+            .unwrap(),
+        recorded[2]
+    );
+    assert!(replay
+        .post(LCD, "txs", json!({ "tx_bytes": "AQ==" }))
+        .await
+        .is_err());
+    assert!(replay.get(LCD, "blocks/1").await.is_err());
+}
+
+#[tokio::test]
+async fn writes_the_fixture_when_dropped() {
+    let path = fixture("dropped");
+    let recorder = RecordingTransport::new(&path).with_transport(Arc::new(Lcd::default()));
+    recorder.finish().unwrap();
+    let response = recorder.get(LCD, "node_info").await.unwrap();
+    drop(recorder);
+
+    let replay = ReplayTransport::load(&path).unwrap();
+    assert_eq!(replay.get(LCD, "node_info").await.unwrap(), response);
+}